@@ -170,11 +170,16 @@ fn main() {
     println!("cargo::rerun-if-changed=./src/**/*.rs");
     println!("cargo::rerun-if-changed=./src/**/*.proto");
 
-    prost_build::compile_protos(
-        &["src/cp_model.proto", "src/sat_parameters.proto"],
-        &["src/"],
-    )
-    .unwrap();
+    let mut config = prost_build::Config::new();
+    if env::var("CARGO_FEATURE_SERDE").is_ok() {
+        config.type_attribute(".", "#[derive(serde::Serialize, serde::Deserialize)]");
+    }
+    config
+        .compile_protos(
+            &["src/cp_model.proto", "src/sat_parameters.proto"],
+            &["src/"],
+        )
+        .unwrap();
 
     if std::env::var("DOCS_RS").is_err() {
         let ortools_lib = Repository::get().unwrap();
@@ -0,0 +1,196 @@
+//! Command line interface to solve a model stored in a file.
+//!
+//! Run `cp-sat --help` for the usage.
+
+use cp_sat::builder::{CpModelBuilder, Format};
+use cp_sat::formats::{dimacs, fzn, lp, mip::MipOptions, mps, opb};
+use cp_sat::prost::Message;
+use cp_sat::stats::SolveStats;
+use cp_sat::{ffi, proto};
+use std::path::PathBuf;
+use std::process::exit;
+
+const USAGE: &str = "\
+Usage: cp-sat [OPTIONS] <MODEL>
+
+Solves the model and writes the response, in the protobuf text format
+on the standard output by default, and statistics on the standard
+error.
+
+The model can be a CpModelProto in the binary (.pb, .bin, .binpb), text
+(.pbtxt, .textproto, .prototxt, .txt) or JSON (.json) format, or a
+mps, lp, cnf, wcnf, opb or fzn file. For fzn files, the solution is
+written in the FlatZinc format instead of the response.
+
+Options:
+      --input-format <FORMAT>   Format of the model, instead of guessing it
+                                from the extension: pb, pbtxt, json, mps,
+                                mps-fixed, lp, cnf, wcnf, opb or fzn
+      --params <TEXT>           SatParameters in the protobuf text format
+      --time-limit <SECONDS>    Maximum solving time, in seconds
+      --workers <N>             Number of search workers
+      --continuous-scale <N>    Discretize continuous mps and lp columns with
+                                a step of 1/N, instead of rejecting them
+  -o, --output <PATH>           Writes the response to PATH, in the format
+                                given by its extension
+  -q, --quiet                   Does not write the statistics
+  -h, --help                    Prints this help
+";
+
+struct Args {
+    model: PathBuf,
+    input_format: Option<String>,
+    params: proto::SatParameters,
+    continuous_scale: Option<i64>,
+    output: Option<PathBuf>,
+    quiet: bool,
+}
+
+fn fail(message: impl std::fmt::Display) -> ! {
+    eprintln!("cp-sat: {}", message);
+    exit(2)
+}
+
+fn number<T: std::str::FromStr>(name: &str, value: String) -> T {
+    value
+        .parse()
+        .unwrap_or_else(|_| fail(format!("invalid value {:?} for {}", value, name)))
+}
+
+fn parse_args() -> Args {
+    let mut args = std::env::args().skip(1);
+    let mut model = None;
+    let mut input_format = None;
+    let mut params_text = None;
+    let mut time_limit = None;
+    let mut workers = None;
+    let mut continuous_scale = None;
+    let mut output = None;
+    let mut quiet = false;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .unwrap_or_else(|| fail(format!("missing value for {}", name)))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                exit(0)
+            }
+            "--input-format" => input_format = Some(value(&arg)),
+            "--params" => params_text = Some(value(&arg)),
+            "--time-limit" => time_limit = Some(number::<f64>(&arg, value(&arg))),
+            "--workers" => workers = Some(number::<i32>(&arg, value(&arg))),
+            "--continuous-scale" => continuous_scale = Some(number::<i64>(&arg, value(&arg))),
+            "-o" | "--output" => output = Some(PathBuf::from(value(&arg))),
+            "-q" | "--quiet" => quiet = true,
+            _ if arg.starts_with('-') => fail(format!("unknown option {}\n\n{}", arg, USAGE)),
+            _ if model.is_none() => model = Some(PathBuf::from(arg)),
+            _ => fail(format!("unexpected argument {}", arg)),
+        }
+    }
+
+    let mut params = match params_text {
+        Some(text) => ffi::from_text_format::<proto::SatParameters>(&text)
+            .unwrap_or_else(|| fail("invalid --params")),
+        None => proto::SatParameters::default(),
+    };
+    if let Some(time_limit) = time_limit {
+        params.max_time_in_seconds = Some(time_limit);
+    }
+    if let Some(workers) = workers {
+        params.num_search_workers = Some(workers);
+    }
+    Args {
+        model: model.unwrap_or_else(|| fail(format!("missing model\n\n{}", USAGE))),
+        input_format,
+        params,
+        continuous_scale,
+        output,
+        quiet,
+    }
+}
+
+enum Model {
+    Builder(CpModelBuilder),
+    /// Keeps the FlatZinc outputs to write the solution.
+    FlatZinc(fzn::FznModel),
+}
+
+fn load(args: &Args) -> Result<Model, String> {
+    let extension = args
+        .model
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    let options = MipOptions {
+        continuous_scale: args.continuous_scale,
+        ..Default::default()
+    };
+    let path = &args.model;
+    let model = match args.input_format.as_deref().or(extension.as_deref()) {
+        Some("mps") => mps::read(path, &options).map(|m| m.model),
+        Some("mps-fixed") => mps::read_fixed(path, &options).map(|m| m.model),
+        Some("lp") => lp::read(path, &options).map(|m| m.model),
+        Some("cnf") | Some("wcnf") => dimacs::read(path).map(|m| m.model),
+        Some("opb") => opb::read(path).map(|m| m.model),
+        Some("fzn") => {
+            return fzn::read(path)
+                .map(Model::FlatZinc)
+                .map_err(|e| e.to_string())
+        }
+        Some("pb") => return read_proto(path, Some(Format::Binary)),
+        Some("pbtxt") => return read_proto(path, Some(Format::Text)),
+        Some("json") => return read_proto(path, Some(Format::Json)),
+        Some(format) if args.input_format.is_some() => {
+            return Err(format!("unknown input format {}", format))
+        }
+        _ => return read_proto(path, None),
+    };
+    model.map(Model::Builder).map_err(|e| e.to_string())
+}
+
+/// Reads a CpModelProto in the given format, or guesses it from the
+/// extension and the content.
+fn read_proto(path: &std::path::Path, format: Option<Format>) -> Result<Model, String> {
+    match format {
+        Some(format) => CpModelBuilder::read_from_with_format(path, format),
+        None => CpModelBuilder::read_from(path),
+    }
+    .map(Model::Builder)
+    .map_err(|e| e.to_string())
+}
+
+fn main() {
+    let args = parse_args();
+    let model = load(&args)
+        .unwrap_or_else(|e| fail(format!("cannot read {}: {}", args.model.display(), e)));
+    let builder = match &model {
+        Model::Builder(builder) => builder,
+        Model::FlatZinc(fzn) => &fzn.model,
+    };
+
+    let response = ffi::solve_with_parameters(builder.proto(), &args.params);
+
+    if !args.quiet {
+        eprintln!("{}", SolveStats::from_response(&response));
+    }
+    match &args.output {
+        Some(path) => {
+            let bytes = match Format::from_path(path).unwrap_or(Format::Text) {
+                Format::Binary => response.encode_to_vec(),
+                Format::Text => ffi::to_text_format(&response).into_bytes(),
+                Format::Json => ffi::to_json(&response).into_bytes(),
+            };
+            std::fs::write(path, bytes)
+                .unwrap_or_else(|e| fail(format!("cannot write {}: {}", path.display(), e)));
+        }
+        None if matches!(model, Model::Builder(_)) => {
+            print!("{}", ffi::to_text_format(&response))
+        }
+        None => {}
+    }
+    if let Model::FlatZinc(fzn) = &model {
+        print!("{}", fzn.output(&response));
+    }
+}
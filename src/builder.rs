@@ -1,6 +1,12 @@
-use crate::{ffi, proto};
+use crate::numeric::integer_multiplier;
+use crate::stats::{ConstraintKind, ModelStats};
+use crate::validation::Location;
+use crate::{dot, ffi, lint, proto, refs, validation};
+use prost::Message;
 use proto::constraint_proto::Constraint as CstEnum;
 use smallvec::SmallVec;
+use std::collections::HashMap;
+use std::sync::OnceLock;
 
 /// A builder for CP SAT.
 ///
@@ -18,9 +24,25 @@ use smallvec::SmallVec;
 /// assert!(x.solution_value(&response));
 /// assert!(y.solution_value(&response));
 /// ```
-#[derive(Default, Debug)]
+#[derive(Clone, Default, Debug)]
 pub struct CpModelBuilder {
     proto: proto::CpModelProto,
+    /// The last solution found by [CpModelBuilder::resolve].
+    last_solution: Vec<i64>,
+    /// Index of the names, built on the first lookup.
+    names: OnceLock<NameIndex>,
+    /// Whether duplicate names are rejected, see
+    /// [CpModelBuilder::set_strict_names].
+    strict_names: bool,
+    /// The variables of [CpModelBuilder::new_constant] by value.
+    constants: HashMap<i64, i32>,
+    /// The literal fixed to false of
+    /// [CpModelBuilder::disable_constraint], never returned to the
+    /// caller.
+    disabling_literal: Option<i32>,
+    /// The linear constraints `start + size == end` and `size >= 0`
+    /// added by [CpModelBuilder::add_interval], by interval index.
+    interval_linears: HashMap<usize, [usize; 2]>,
 }
 
 impl CpModelBuilder {
@@ -29,6 +51,12 @@ impl CpModelBuilder {
         &self.proto
     }
 
+    pub(crate) fn proto_mut(&mut self) -> &mut proto::CpModelProto {
+        // the names may be modified
+        self.names = OnceLock::new();
+        &mut self.proto
+    }
+
     /// Creates a new boolean variable, and returns the [BoolVar]
     /// indentifier.
     ///
@@ -68,12 +96,14 @@ impl CpModelBuilder {
     /// let x = model.new_bool_var_with_name("x");
     /// assert_eq!("x", model.var_name(x));
     /// ```
+    #[track_caller]
     pub fn new_bool_var_with_name(&mut self, name: impl Into<String>) -> BoolVar {
         let index = self.proto.variables.len() as i32;
         self.proto.variables.push(proto::IntegerVariableProto {
-            name: name.into(),
+            name: String::new(),
             domain: vec![0, 1],
         });
+        self.rename_var(index as usize, name.into());
         BoolVar(index)
     }
     /// Creates a new integer variable, and returns the [IntVar]
@@ -110,6 +140,7 @@ impl CpModelBuilder {
     /// let x = model.new_int_var_with_name([(0, 10)], "x");
     /// assert_eq!("x", model.var_name(x));
     /// ```
+    #[track_caller]
     pub fn new_int_var_with_name(
         &mut self,
         domain: impl IntoIterator<Item = (i64, i64)>,
@@ -117,536 +148,701 @@ impl CpModelBuilder {
     ) -> IntVar {
         let index = self.proto.variables.len() as i32;
         self.proto.variables.push(proto::IntegerVariableProto {
-            name: name.into(),
+            name: String::new(),
             domain: domain.into_iter().flat_map(|(b, e)| [b, e]).collect(),
         });
+        self.rename_var(index as usize, name.into());
         IntVar(index)
     }
 
-    /// Returns the name of a variable, empty string if not setted.
+    /// Returns an unnamed variable fixed to the given value.
+    ///
+    /// The constants are cached: asking several times for the same
+    /// value returns the same variable, as long as it stays unnamed
+    /// and fixed to this value.
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_bool_var_with_name("x");
-    /// assert_eq!("x", model.var_name(x));
-    /// let y = model.new_int_var([(0, 2)]);
-    /// assert_eq!("", model.var_name(y));
+    /// let ten = model.new_constant(10);
+    /// assert_eq!(ten, model.new_constant(10));
+    /// assert_eq!(model.var_bounds(ten), Some((10, 10)));
+    /// assert_eq!(model.proto().variables.len(), 1);
     /// ```
-    pub fn var_name(&self, var: impl Into<IntVar>) -> &str {
-        &self.proto.variables[var.into().0 as usize].name
+    pub fn new_constant(&mut self, value: i64) -> IntVar {
+        if let Some(&index) = self.constants.get(&value) {
+            let var = &self.proto.variables[index as usize];
+            if var.name.is_empty() && var.domain == [value, value] {
+                return IntVar(index);
+            }
+        }
+        let var = self.new_int_var([(value, value)]);
+        self.constants.insert(value, var.0);
+        var
     }
 
-    /// Sets the variable name.
+    /// Returns a literal fixed to true. It is the negation of
+    /// [CpModelBuilder::new_false], thus both share the same cached
+    /// variable.
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_bool_var();
-    /// model.set_var_name(x, "x");
-    /// assert_eq!("x", model.var_name(x));
+    /// let t = model.new_true();
+    /// let f = model.new_false();
+    /// assert_eq!(t, !f);
+    /// assert_eq!(model.var_domain(t).fixed_value(), Some(1));
+    /// assert_eq!(model.proto().variables.len(), 1);
     /// ```
-    pub fn set_var_name(&mut self, var: impl Into<IntVar>, name: &str) {
-        self.proto.variables[var.into().0 as usize].name = name.into();
+    pub fn new_true(&mut self) -> BoolVar {
+        !self.new_false()
     }
 
-    /// Returns the name of a constraint, empty string if not setted.
+    /// Returns a literal fixed to false, the cached
+    /// [constant][CpModelBuilder::new_constant] 0.
+    pub fn new_false(&mut self) -> BoolVar {
+        BoolVar(self.new_constant(0).0)
+    }
+
+    /// Creates a multi-dimensional array of new boolean variables
+    /// with the given shape, and returns the [VarArray] of their
+    /// identifiers.
+    ///
+    /// The variables are named `prefix[i][j]...` after their index,
+    /// or not named if the prefix is empty.
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_bool_var();
-    /// let constraint = model.add_or([x]);
-    /// assert_eq!("", model.constraint_name(constraint));
-    /// model.set_constraint_name(constraint, "or");
-    /// assert_eq!("or", model.constraint_name(constraint));
+    /// let x = model.new_bool_var_array(&[3, 4], "x");
+    /// assert_eq!(12, x.len());
+    /// assert_eq!("x[2][1]", model.var_name(x[[2, 1]]));
+    /// for i in 0..3 {
+    ///     model.add_exactly_one(x.row(i));
+    /// }
     /// ```
-    pub fn constraint_name(&self, constraint: Constraint) -> &str {
-        &self.proto.constraints[constraint.0].name
+    pub fn new_bool_var_array(&mut self, shape: &[usize], prefix: &str) -> VarArray<BoolVar> {
+        VarArray::from_fn(shape, |index| {
+            self.new_bool_var_with_name(array_var_name(prefix, index))
+        })
     }
 
-    /// Sets the name of a constraint.
+    /// Creates a multi-dimensional array of new integer variables
+    /// with the given shape and domain, and returns the [VarArray] of
+    /// their identifiers.
+    ///
+    /// The variables are named `prefix[i][j]...` after their index,
+    /// or not named if the prefix is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
+    /// let mut model = CpModelBuilder::default();
+    /// let grid = model.new_int_var_array(&[9, 9], [(1, 9)], "cell");
+    /// for i in 0..9 {
+    ///     model.add_all_different(grid.row(i));
+    ///     model.add_all_different(grid.column(i));
+    /// }
+    /// model.add_eq(grid.iter().collect::<LinearExpr>(), 405);
+    /// ```
+    pub fn new_int_var_array(
+        &mut self,
+        shape: &[usize],
+        domain: impl IntoIterator<Item = (i64, i64)>,
+        prefix: &str,
+    ) -> VarArray<IntVar> {
+        let domain: Vec<(i64, i64)> = domain.into_iter().collect();
+        VarArray::from_fn(shape, |index| {
+            self.new_int_var_with_name(domain.iter().copied(), array_var_name(prefix, index))
+        })
+    }
+
+    /// Returns the name of a variable, empty string if not setted.
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_bool_var();
-    /// let constraint = model.add_or([x]);
-    /// model.set_constraint_name(constraint, "or");
-    /// assert_eq!("or", model.constraint_name(constraint));
+    /// let x = model.new_bool_var_with_name("x");
+    /// assert_eq!("x", model.var_name(x));
+    /// let y = model.new_int_var([(0, 2)]);
+    /// assert_eq!("", model.var_name(y));
     /// ```
-    pub fn set_constraint_name(&mut self, constraint: Constraint, name: &str) {
-        self.proto.constraints[constraint.0].name = name.into();
+    pub fn var_name(&self, var: impl Into<IntVar>) -> &str {
+        &self.proto.variables[var.into().0 as usize].name
     }
 
-    /// Adds a boolean OR constraint on a list of [BoolVar].
+    /// Sets the variable name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is used by another variable in the
+    /// [strict naming mode][CpModelBuilder::set_strict_names]. See
+    /// [CpModelBuilder::try_set_var_name] for a fallible version.
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
-    /// # use cp_sat::proto::CpSolverStatus;
     /// let mut model = CpModelBuilder::default();
     /// let x = model.new_bool_var();
-    /// let y = model.new_bool_var();
-    /// model.add_or([x, y]);
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert!(x.solution_value(&response) || y.solution_value(&response));
+    /// model.set_var_name(x, "x");
+    /// assert_eq!("x", model.var_name(x));
     /// ```
-    pub fn add_or(&mut self, vars: impl IntoIterator<Item = BoolVar>) -> Constraint {
-        self.add_cst(CstEnum::BoolOr(proto::BoolArgumentProto {
-            literals: vars.into_iter().map(|v| v.0).collect(),
-        }))
+    #[track_caller]
+    pub fn set_var_name(&mut self, var: impl Into<IntVar>, name: &str) {
+        self.rename_var(var.into().0 as usize, name.into());
     }
 
-    /// Adds a boolean AND constraint on a list of [BoolVar].
+    /// Sets the variable name, or returns an error without renaming
+    /// the variable if the name is used by another variable in the
+    /// [strict naming mode][CpModelBuilder::set_strict_names].
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
-    /// # use cp_sat::proto::CpSolverStatus;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_bool_var();
+    /// let x = model.new_bool_var_with_name("x");
     /// let y = model.new_bool_var();
-    /// model.add_and([x, y]);
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert!(x.solution_value(&response));
-    /// assert!(y.solution_value(&response));
+    /// model.set_strict_names(true).unwrap();
+    /// let error = model.try_set_var_name(y, "x").unwrap_err();
+    /// assert_eq!(error.to_string(), "variable #1: name already used by variable #0");
+    /// assert_eq!("", model.var_name(y));
+    /// assert!(model.try_set_var_name(y, "y").is_ok());
     /// ```
-    pub fn add_and(&mut self, vars: impl IntoIterator<Item = BoolVar>) -> Constraint {
-        self.add_cst(CstEnum::BoolAnd(proto::BoolArgumentProto {
-            literals: vars.into_iter().map(|v| v.0).collect(),
-        }))
+    pub fn try_set_var_name(
+        &mut self,
+        var: impl Into<IntVar>,
+        name: &str,
+    ) -> Result<(), DuplicateNameError> {
+        self.try_rename_var(var.into().0 as usize, name.into())
     }
 
-    /// Adds a boolean "at most one" constraint on a list of [BoolVar].
+    /// Returns the first variable with the given name, if any. The
+    /// names are indexed, thus the lookup does not depend on the size
+    /// of the model. Unnamed variables are never returned.
+    ///
+    /// Useful to recover the variable handles of a model loaded with
+    /// [CpModelBuilder::read_from].
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
-    /// # use cp_sat::proto::CpSolverStatus;
     /// let mut model = CpModelBuilder::default();
-    /// let vars: Vec<_> = (0..10).map(|_| model.new_bool_var()).collect();
-    /// model.add_at_most_one(vars.iter().copied());
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert!(
-    ///     vars.iter()
-    ///         .map(|v| v.solution_value(&response) as u32)
-    ///         .sum::<u32>()
-    ///         <= 1
-    /// );
+    /// let x = model.new_int_var_with_name([(0, 10)], "x");
+    /// assert_eq!(Some(x), model.var_by_name("x"));
+    /// assert_eq!(None, model.var_by_name("y"));
     /// ```
-    pub fn add_at_most_one(&mut self, vars: impl IntoIterator<Item = BoolVar>) -> Constraint {
-        self.add_cst(CstEnum::AtMostOne(proto::BoolArgumentProto {
-            literals: vars.into_iter().map(|v| v.0).collect(),
-        }))
+    pub fn var_by_name(&self, name: &str) -> Option<IntVar> {
+        self.names()
+            .vars
+            .get(name)
+            .map(|indices| IntVar(indices[0] as i32))
     }
 
-    /// Adds a boolean "exactly one" constraint on a list of [BoolVar].
+    /// Returns the first variable with the given name as a
+    /// [BoolVar], if any. Returns `None` if the domain of the
+    /// variable is not included in [0, 1].
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
-    /// # use cp_sat::proto::CpSolverStatus;
     /// let mut model = CpModelBuilder::default();
-    /// let vars: Vec<_> = (0..10).map(|_| model.new_bool_var()).collect();
-    /// model.add_exactly_one(vars.iter().copied());
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert!(
-    ///     vars.iter()
-    ///         .map(|v| v.solution_value(&response) as u32)
-    ///         .sum::<u32>()
-    ///         == 1
-    /// );
+    /// let x = model.new_bool_var_with_name("x");
+    /// let _y = model.new_int_var_with_name([(0, 10)], "y");
+    /// assert_eq!(Some(x), model.bool_var_by_name("x"));
+    /// assert_eq!(None, model.bool_var_by_name("y"));
     /// ```
-    pub fn add_exactly_one(&mut self, vars: impl IntoIterator<Item = BoolVar>) -> Constraint {
-        self.add_cst(CstEnum::ExactlyOne(proto::BoolArgumentProto {
-            literals: vars.into_iter().map(|v| v.0).collect(),
-        }))
+    pub fn bool_var_by_name(&self, name: &str) -> Option<BoolVar> {
+        let var = self.var_by_name(name)?;
+        let domain = &self.proto.variables[var.0 as usize].domain;
+        match (domain.first(), domain.last()) {
+            (Some(&lb), Some(&ub)) if 0 <= lb && ub <= 1 => Some(BoolVar(var.0)),
+            _ => None,
+        }
     }
 
-    /// Adds a boolean XOR constraint on a list of [BoolVar].
+    /// Returns the current domain of a variable.
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
-    /// # use cp_sat::proto::CpSolverStatus;
     /// let mut model = CpModelBuilder::default();
-    /// let vars: Vec<_> = (0..10).map(|_| model.new_bool_var()).collect();
-    /// model.add_xor(vars.iter().copied());
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert!(
-    ///     vars.iter()
-    ///         .map(|v| v.solution_value(&response) as u32)
-    ///         .sum::<u32>()
-    ///         % 2
-    ///         == 1
-    /// );
+    /// let x = model.new_int_var([(0, 2), (4, 8)]);
+    /// let b = model.new_bool_var();
+    /// let domain = model.var_domain(x);
+    /// assert_eq!(domain.to_string(), "[0, 2] ∪ [4, 8]");
+    /// assert!(domain.contains(5) && !domain.contains(3));
+    /// // the domain can be reused for other variables
+    /// let y = model.new_int_var(model.var_domain(x));
+    /// assert_eq!(model.var_domain(y), domain);
+    /// model.set_var_domain(b, [(1, 1)]);
+    /// assert_eq!(model.var_domain(!b).fixed_value(), Some(0));
     /// ```
-    pub fn add_xor(&mut self, vars: impl IntoIterator<Item = BoolVar>) -> Constraint {
-        self.add_cst(CstEnum::BoolXor(proto::BoolArgumentProto {
-            literals: vars.into_iter().map(|v| v.0).collect(),
-        }))
+    pub fn var_domain(&self, var: impl Into<IntVar>) -> Domain {
+        let var = var.into();
+        let domain = Domain(self.proto.variables[refs::var_index(var.0)].domain.clone());
+        if var.0 < 0 {
+            domain.negated()
+        } else {
+            domain
+        }
     }
 
-    /// Adds a "all different" constraint on a list of [BoolVar].
+    /// Returns the lower and upper bounds of a variable, or `None` if
+    /// its domain is empty.
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
-    /// # use cp_sat::proto::CpSolverStatus;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_int_var([(0, 2)]);
-    /// let y = model.new_int_var([(0, 2)]);
-    /// let z = model.new_int_var([(0, 2)]);
-    /// model.add_all_different([x, y, z]);
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// let x_val = x.solution_value(&response);
-    /// let y_val = y.solution_value(&response);
-    /// let z_val = z.solution_value(&response);
-    /// assert!(x_val != y_val);
-    /// assert!(x_val != z_val);
-    /// assert!(y_val != z_val);
+    /// let x = model.new_int_var([(0, 2), (4, 8)]);
+    /// assert_eq!(model.var_bounds(x), Some((0, 8)));
+    /// model.tighten_var_domain(x, [(3, 3)]);
+    /// assert_eq!(model.var_bounds(x), None);
     /// ```
-    pub fn add_all_different(&mut self, vars: impl IntoIterator<Item = IntVar>) -> Constraint {
-        self.add_cst(CstEnum::AllDiff(proto::AllDifferentConstraintProto {
-            vars: vars.into_iter().map(|v| v.0).collect(),
-        }))
+    pub fn var_bounds(&self, var: impl Into<IntVar>) -> Option<(i64, i64)> {
+        let domain = self.var_domain(var);
+        Some((domain.min()?, domain.max()?))
     }
 
-    /// Adds a linear constraint.
+    /// Returns true if the domain of a variable has a single value.
     ///
-    /// # Exemple
+    /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
-    /// # use cp_sat::proto::CpSolverStatus;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_int_var([(0, 100)]);
-    /// let y = model.new_int_var([(0, 100)]);
-    /// model.add_linear_constraint([(1, x), (3, y)], [(301, i64::MAX)]);
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert!(x.solution_value(&response) + 3 * y.solution_value(&response) >= 301);
+    /// let x = model.new_int_var([(0, 10)]);
+    /// let one = model.new_constant(1);
+    /// assert!(!model.is_fixed(x));
+    /// assert!(model.is_fixed(one));
     /// ```
-    pub fn add_linear_constraint(
-        &mut self,
-        expr: impl Into<LinearExpr>,
-        domain: impl IntoIterator<Item = (i64, i64)>,
-    ) -> Constraint {
-        let expr = expr.into();
-        let constant = expr.constant;
-        self.add_cst(CstEnum::Linear(proto::LinearConstraintProto {
-            vars: expr.vars.into_vec(),
-            coeffs: expr.coeffs.into_vec(),
-            domain: domain
-                .into_iter()
-                .flat_map(|(begin, end)| {
-                    [
-                        if begin == i64::MIN {
-                            i64::MIN
-                        } else {
-                            begin.saturating_sub(constant)
-                        },
-                        if end == i64::MAX {
-                            i64::MAX
-                        } else {
-                            end.saturating_sub(constant)
-                        },
-                    ]
-                })
-                .collect(),
-        }))
+    pub fn is_fixed(&self, var: impl Into<IntVar>) -> bool {
+        self.var_domain(var).fixed_value().is_some()
     }
 
-    /// Adds an equality constraint between 2 linear expressions.
+    /// Replaces the domain of a variable.
     ///
-    /// # Exemple
+    /// # Example
     ///
     /// ```
-    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
-    /// # use cp_sat::proto::CpSolverStatus;
+    /// # use cp_sat::builder::CpModelBuilder;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_int_var([(0, 50)]);
-    /// let y = model.new_int_var([(53, 100)]);
-    /// model.add_eq(y, LinearExpr::from(x) + 3);
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert_eq!(y.solution_value(&response), x.solution_value(&response) + 3);
-    /// assert_eq!(50, x.solution_value(&response));
-    /// assert_eq!(53, y.solution_value(&response));
+    /// let x = model.new_int_var([(0, 10)]);
+    /// model.set_var_domain(x, [(5, 20)]);
+    /// assert_eq!(model.proto().variables[0].domain, [5, 20]);
     /// ```
-    pub fn add_eq<T: Into<LinearExpr>, U: Into<LinearExpr>>(
+    pub fn set_var_domain(
         &mut self,
-        lhs: T,
-        rhs: U,
-    ) -> Constraint {
-        self.add_linear_constraint(lhs.into() - rhs.into(), [(0, 0)])
+        var: impl Into<IntVar>,
+        domain: impl IntoIterator<Item = (i64, i64)>,
+    ) {
+        let var = var.into();
+        let domain = Domain::literal(var, domain);
+        self.proto.variables[refs::var_index(var.0)].domain = domain.0;
     }
 
-    /// Adds a greater or equal constraint between 2 linear expressions.
+    /// Restricts the domain of a variable to its intersection with
+    /// the given domain. The domain becomes empty, making the model
+    /// infeasible, if the intersection is empty.
     ///
-    /// # Exemple
+    /// # Example
     ///
     /// ```
-    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
-    /// # use cp_sat::proto::CpSolverStatus;
+    /// # use cp_sat::builder::CpModelBuilder;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_int_var([(0, 50)]);
-    /// let y = model.new_int_var([(50, 100)]);
-    /// model.add_ge(x, y);
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert!(x.solution_value(&response) >= y.solution_value(&response));
-    /// assert_eq!(50, x.solution_value(&response));
-    /// assert_eq!(50, y.solution_value(&response));
+    /// let x = model.new_int_var([(0, 10), (20, 30)]);
+    /// model.tighten_var_domain(x, [(5, 25)]);
+    /// assert_eq!(model.proto().variables[0].domain, [5, 10, 20, 25]);
     /// ```
-    pub fn add_ge<T: Into<LinearExpr>, U: Into<LinearExpr>>(
+    pub fn tighten_var_domain(
         &mut self,
-        lhs: T,
-        rhs: U,
-    ) -> Constraint {
-        self.add_linear_constraint(lhs.into() - rhs.into(), [(0, i64::MAX)])
+        var: impl Into<IntVar>,
+        domain: impl IntoIterator<Item = (i64, i64)>,
+    ) {
+        let var = var.into();
+        let domain = Domain::literal(var, domain);
+        let var = &mut self.proto.variables[refs::var_index(var.0)];
+        var.domain = intersect_domains(&var.domain, &domain.0);
     }
 
-    /// Adds a lesser or equal constraint between 2 linear expressions.
+    /// Returns the name of a constraint, empty string if not setted.
     ///
-    /// # Exemple
+    /// # Example
     ///
     /// ```
-    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
-    /// # use cp_sat::proto::CpSolverStatus;
+    /// # use cp_sat::builder::CpModelBuilder;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_int_var([(50, 100)]);
-    /// let y = model.new_int_var([(0, 50)]);
-    /// model.add_le(x, y);
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert!(x.solution_value(&response) <= y.solution_value(&response));
-    /// assert_eq!(50, x.solution_value(&response));
-    /// assert_eq!(50, y.solution_value(&response));
+    /// let x = model.new_bool_var();
+    /// let constraint = model.add_or([x]);
+    /// assert_eq!("", model.constraint_name(constraint));
+    /// model.set_constraint_name(constraint, "or");
+    /// assert_eq!("or", model.constraint_name(constraint));
     /// ```
-    pub fn add_le<T: Into<LinearExpr>, U: Into<LinearExpr>>(
-        &mut self,
-        lhs: T,
-        rhs: U,
-    ) -> Constraint {
-        self.add_linear_constraint(lhs.into() - rhs.into(), [(i64::MIN, 0)])
+    pub fn constraint_name(&self, constraint: Constraint) -> &str {
+        &self.proto.constraints[constraint.0].name
     }
 
-    /// Adds a stricly greater constraint between 2 linear expressions.
+    /// Sets the name of a constraint.
     ///
-    /// # Exemple
+    /// # Panics
+    ///
+    /// Panics if the name is used by another constraint in the
+    /// [strict naming mode][CpModelBuilder::set_strict_names]. See
+    /// [CpModelBuilder::try_set_constraint_name] for a fallible
+    /// version.
+    ///
+    /// # Example
     ///
     /// ```
-    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
-    /// # use cp_sat::proto::CpSolverStatus;
+    /// # use cp_sat::builder::CpModelBuilder;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_int_var([(0, 51)]);
-    /// let y = model.new_int_var([(50, 100)]);
-    /// model.add_gt(x, y);
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert!(x.solution_value(&response) > y.solution_value(&response));
-    /// assert_eq!(51, x.solution_value(&response));
-    /// assert_eq!(50, y.solution_value(&response));
+    /// let x = model.new_bool_var();
+    /// let constraint = model.add_or([x]);
+    /// model.set_constraint_name(constraint, "or");
+    /// assert_eq!("or", model.constraint_name(constraint));
     /// ```
-    pub fn add_gt<T: Into<LinearExpr>, U: Into<LinearExpr>>(
-        &mut self,
-        lhs: T,
-        rhs: U,
-    ) -> Constraint {
-        self.add_linear_constraint(lhs.into() - rhs.into(), [(1, i64::MAX)])
+    #[track_caller]
+    pub fn set_constraint_name(&mut self, constraint: Constraint, name: &str) {
+        self.rename_constraint(constraint.0, name.into());
     }
 
-    /// Adds a strictly lesser constraint between 2 linear expressions.
+    /// Sets the name of a constraint, or returns an error without
+    /// renaming the constraint if the name is used by another
+    /// constraint in the [strict naming
+    /// mode][CpModelBuilder::set_strict_names].
     ///
-    /// # Exemple
+    /// # Example
     ///
     /// ```
-    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
-    /// # use cp_sat::proto::CpSolverStatus;
+    /// # use cp_sat::builder::CpModelBuilder;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_int_var([(50, 100)]);
-    /// let y = model.new_int_var([(0, 51)]);
-    /// model.add_lt(x, y);
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert!(x.solution_value(&response) < y.solution_value(&response));
-    /// assert_eq!(50, x.solution_value(&response));
-    /// assert_eq!(51, y.solution_value(&response));
+    /// let x = model.new_bool_var();
+    /// let c1 = model.add_or([x]);
+    /// let c2 = model.add_and([x]);
+    /// model.set_constraint_name(c1, "c");
+    /// model.set_strict_names(true).unwrap();
+    /// assert!(model.try_set_constraint_name(c2, "c").is_err());
+    /// assert_eq!("", model.constraint_name(c2));
     /// ```
-    pub fn add_lt<T: Into<LinearExpr>, U: Into<LinearExpr>>(
+    pub fn try_set_constraint_name(
         &mut self,
-        lhs: T,
-        rhs: U,
-    ) -> Constraint {
-        self.add_linear_constraint(lhs.into() - rhs.into(), [(i64::MIN, -1)])
+        constraint: Constraint,
+        name: &str,
+    ) -> Result<(), DuplicateNameError> {
+        self.try_rename_constraint(constraint.0, name.into())
     }
 
-    /// Adds a not equal constraint between 2 linear expressions.
+    /// Returns the first constraint with the given name, if any.
+    /// Unnamed constraints are never returned.
     ///
-    /// # Exemple
+    /// # Example
     ///
     /// ```
-    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
-    /// # use cp_sat::proto::CpSolverStatus;
+    /// # use cp_sat::builder::CpModelBuilder;
     /// let mut model = CpModelBuilder::default();
     /// let x = model.new_bool_var();
-    /// model.add_ne(x, 1);
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert!(x.solution_value(&response) as i64 != 1);
-    /// assert!(!x.solution_value(&response));
+    /// let constraint = model.add_or([x]);
+    /// model.set_constraint_name(constraint, "or");
+    /// assert_eq!(Some(constraint), model.constraint_by_name("or"));
+    /// assert_eq!(None, model.constraint_by_name("and"));
     /// ```
-    pub fn add_ne<T: Into<LinearExpr>, U: Into<LinearExpr>>(
-        &mut self,
-        lhs: T,
-        rhs: U,
-    ) -> Constraint {
-        self.add_linear_constraint(lhs.into() - rhs.into(), [(i64::MIN, -1), (1, i64::MAX)])
+    pub fn constraint_by_name(&self, name: &str) -> Option<Constraint> {
+        self.names()
+            .constraints
+            .get(name)
+            .map(|indices| Constraint(indices[0]))
     }
 
-    /// Adds a constraint that force the `target` to be equal to the
-    /// minimum of the given `exprs`.
+    /// Enables or disables the strict naming mode, disabled by
+    /// default.
     ///
-    /// # Exemple
+    /// In strict mode, giving to a variable the name of another
+    /// variable, or to a constraint the name of another constraint,
+    /// panics, or fails with the `try_` methods such as
+    /// [CpModelBuilder::try_set_var_name]. Empty names are not concerned. Enabling the strict
+    /// mode fails if the model already has duplicate names, e.g. for
+    /// a model loaded from a file, and returns the first duplicate.
+    ///
+    /// # Example
     ///
     /// ```
-    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
-    /// # use cp_sat::proto::CpSolverStatus;
+    /// # use cp_sat::builder::CpModelBuilder;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_int_var([(0, 10)]);
-    /// let y = model.new_int_var([(5, 15)]);
-    /// let m = model.new_int_var([(-100, 100)]);
-    /// model.add_min_eq(m, [x, y]);
-    /// model.maximize(m);
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert_eq!(10., response.objective_value);
-    /// assert_eq!(10, m.solution_value(&response));
+    /// let x = model.new_bool_var_with_name("x");
+    /// let y = model.new_bool_var_with_name("x");
+    /// let error = model.set_strict_names(true).unwrap_err();
+    /// assert_eq!(error.to_string(), "variable #1 (x): name already used by variable #0");
+    /// model.set_var_name(y, "y");
+    /// assert!(model.set_strict_names(true).is_ok());
+    /// let result = std::panic::catch_unwind(move || model.new_bool_var_with_name("x"));
+    /// assert!(result.is_err());
     /// ```
-    pub fn add_min_eq(
+    pub fn set_strict_names(&mut self, strict: bool) -> Result<(), DuplicateNameError> {
+        if strict {
+            let names = self.names();
+            let duplicate =
+                |indices: &Vec<usize>, name: &String| !name.is_empty() && indices.len() > 1;
+            let var = names
+                .vars
+                .iter()
+                .filter(|(name, indices)| duplicate(indices, name))
+                .map(|(_, indices)| (indices[1], indices[0]))
+                .min();
+            if let Some((index, first)) = var {
+                return Err(DuplicateNameError {
+                    location: Location::variable(&self.proto, index),
+                    first: Location::variable(&self.proto, first),
+                });
+            }
+            let constraint = names
+                .constraints
+                .iter()
+                .filter(|(name, indices)| duplicate(indices, name))
+                .map(|(_, indices)| (indices[1], indices[0]))
+                .min();
+            if let Some((index, first)) = constraint {
+                return Err(DuplicateNameError {
+                    location: Location::constraint(&self.proto, index),
+                    first: Location::constraint(&self.proto, first),
+                });
+            }
+        }
+        self.strict_names = strict;
+        Ok(())
+    }
+
+    fn names(&self) -> &NameIndex {
+        self.names.get_or_init(|| NameIndex::new(&self.proto))
+    }
+
+    #[track_caller]
+    fn rename_var(&mut self, index: usize, name: String) {
+        if let Err(error) = self.try_rename_var(index, name) {
+            panic!("{}", error);
+        }
+    }
+
+    fn try_rename_var(&mut self, index: usize, name: String) -> Result<(), DuplicateNameError> {
+        if self.strict_names && !name.is_empty() {
+            if let Some(&first) = self
+                .names()
+                .vars
+                .get(&name)
+                .and_then(|indices| indices.iter().find(|&&i| i != index))
+            {
+                return Err(DuplicateNameError {
+                    location: Location::variable(&self.proto, index),
+                    first: Location::variable(&self.proto, first),
+                });
+            }
+        }
+        let var = &mut self.proto.variables[index];
+        if let Some(names) = self.names.get_mut() {
+            NameIndex::remove(&mut names.vars, &var.name, index);
+            NameIndex::insert(&mut names.vars, &name, index);
+        }
+        var.name = name;
+        Ok(())
+    }
+
+    #[track_caller]
+    fn rename_constraint(&mut self, index: usize, name: String) {
+        if let Err(error) = self.try_rename_constraint(index, name) {
+            panic!("{}", error);
+        }
+    }
+
+    fn try_rename_constraint(
         &mut self,
-        target: impl Into<LinearExpr>,
-        exprs: impl IntoIterator<Item = impl Into<LinearExpr>>,
-    ) -> Constraint {
-        self.add_cst(CstEnum::LinMin(proto::LinearArgumentProto {
-            target: Some(target.into().into()),
-            exprs: exprs.into_iter().map(|e| e.into().into()).collect(),
-        }))
+        index: usize,
+        name: String,
+    ) -> Result<(), DuplicateNameError> {
+        if self.strict_names && !name.is_empty() {
+            if let Some(&first) = self
+                .names()
+                .constraints
+                .get(&name)
+                .and_then(|indices| indices.iter().find(|&&i| i != index))
+            {
+                return Err(DuplicateNameError {
+                    location: Location::constraint(&self.proto, index),
+                    first: Location::constraint(&self.proto, first),
+                });
+            }
+        }
+        let cst = &mut self.proto.constraints[index];
+        if let Some(names) = self.names.get_mut() {
+            NameIndex::remove(&mut names.constraints, &cst.name, index);
+            NameIndex::insert(&mut names.constraints, &name, index);
+        }
+        cst.name = name;
+        Ok(())
     }
 
-    /// Adds a constraint that force the `target` to be equal to the
-    /// maximum of the given `exprs`.
+    /// Returns the handles of all the constraints of the model, in
+    /// order of creation.
     ///
-    /// # Exemple
+    /// # Example
     ///
     /// ```
-    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_bool_var();
+    /// let c1 = model.add_or([x]);
+    /// let c2 = model.add_and([x]);
+    /// model.set_constraint_name(c2, "line1/and");
+    /// let line1: Vec<_> = model
+    ///     .constraints()
+    ///     .filter(|&c| model.constraint_name(c).starts_with("line1/"))
+    ///     .collect();
+    /// assert_eq!(line1, [c2]);
+    /// assert_eq!(model.constraints().collect::<Vec<_>>(), [c1, c2]);
+    /// ```
+    pub fn constraints(&self) -> impl Iterator<Item = Constraint> {
+        (0..self.proto.constraints.len()).map(Constraint)
+    }
+
+    /// Removes a constraint, by replacing it with an empty
+    /// constraint: the indices of the other constraints are kept.
+    ///
+    /// An interval constraint must not be removed while it is used by
+    /// other constraints. The empty constraints left by this method
+    /// are ignored by [lint][crate::lint::lint].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_bool_var();
+    /// let constraint = model.add_and([x, !x]);
+    /// model.remove_constraint(constraint);
+    /// assert!(model.proto().constraints[0].constraint.is_none());
+    /// ```
+    pub fn remove_constraint(&mut self, constraint: Constraint) {
+        self.rename_constraint(constraint.0, String::new());
+        self.proto.constraints[constraint.0] = Default::default();
+        self.interval_linears.remove(&constraint.0);
+    }
+
+    /// Disables a constraint by enforcing it with a literal fixed to
+    /// false, and returns true. It can be enabled again with
+    /// [CpModelBuilder::enable_constraint].
+    ///
+    /// Only boolean OR, boolean AND and linear constraints support
+    /// enforcement literals: for the other constraints, nothing is
+    /// done and false is returned. Use
+    /// [CpModelBuilder::remove_constraint] instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
     /// # use cp_sat::proto::CpSolverStatus;
     /// let mut model = CpModelBuilder::default();
     /// let x = model.new_int_var([(0, 10)]);
-    /// let y = model.new_int_var([(5, 15)]);
-    /// let m = model.new_int_var([(-100, 100)]);
-    /// model.add_max_eq(m, [x, y]);
-    /// model.minimize(m);
+    /// let constraint = model.add_le(x, 5);
+    /// model.maximize(x);
+    /// assert!(model.disable_constraint(constraint));
+    /// assert!(model.is_constraint_disabled(constraint));
     /// let response = model.solve();
     /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert_eq!(5., response.objective_value);
-    /// assert_eq!(5, m.solution_value(&response));
+    /// assert_eq!(10, x.solution_value(&response));
     /// ```
-    pub fn add_max_eq(
-        &mut self,
-        target: impl Into<LinearExpr>,
-        exprs: impl IntoIterator<Item = impl Into<LinearExpr>>,
-    ) -> Constraint {
-        self.add_cst(CstEnum::LinMax(proto::LinearArgumentProto {
-            target: Some(target.into().into()),
-            exprs: exprs.into_iter().map(|e| e.into().into()).collect(),
-        }))
+    pub fn disable_constraint(&mut self, constraint: Constraint) -> bool {
+        let kind = ConstraintKind::of(&self.proto.constraints[constraint.0]);
+        if !matches!(
+            kind,
+            ConstraintKind::BoolOr | ConstraintKind::BoolAnd | ConstraintKind::Linear
+        ) {
+            return false;
+        }
+        if !self.is_constraint_disabled(constraint) {
+            let literal = self.disabling_literal();
+            self.proto.constraints[constraint.0]
+                .enforcement_literal
+                .push(literal);
+        }
+        true
     }
-    fn add_cst(&mut self, cst: CstEnum) -> Constraint {
-        let index = self.proto.constraints.len();
-        self.proto.constraints.push(proto::ConstraintProto {
-            constraint: Some(cst),
-            ..Default::default()
-        });
-        Constraint(index)
+
+    /// Returns the private literal fixed to false used to disable
+    /// constraints. Contrary to [CpModelBuilder::new_false], it is not
+    /// shared with the caller, who cannot relax it by mistake.
+    fn disabling_literal(&mut self) -> i32 {
+        match self.disabling_literal {
+            Some(literal) if is_false_literal(&self.proto.variables, literal) => literal,
+            _ => {
+                let literal = self.new_int_var([(0, 0)]).0;
+                self.disabling_literal = Some(literal);
+                literal
+            }
+        }
     }
 
-    /// Add a solution hint.
+    /// Enables a constraint disabled by
+    /// [CpModelBuilder::disable_constraint].
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
-    /// # use cp_sat::proto::CpSolverStatus;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_int_var([(0, 100)]);
-    /// let y = model.new_bool_var();
-    /// model.add_hint(x, 42);
-    /// model.add_hint(y, 1);
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// let x = model.new_bool_var();
+    /// let constraint = model.add_or([x]);
+    /// model.disable_constraint(constraint);
+    /// model.enable_constraint(constraint);
+    /// assert!(!model.is_constraint_disabled(constraint));
+    /// assert!(model.proto().constraints[0].enforcement_literal.is_empty());
     /// ```
-    pub fn add_hint(&mut self, var: impl Into<IntVar>, value: i64) {
-        let var = var.into();
-        let hints = self
-            .proto
-            .solution_hint
-            .get_or_insert_with(Default::default);
-        if var.0 < 0 {
-            hints.vars.push(var.not().0);
-            hints.values.push(1 - value);
-        } else {
-            hints.vars.push(var.0);
-            hints.values.push(value);
+    pub fn enable_constraint(&mut self, constraint: Constraint) {
+        if let Some(literal) = self.disabling_literal {
+            self.proto.constraints[constraint.0]
+                .enforcement_literal
+                .retain(|&l| l != literal);
         }
     }
 
-    /// Delete all solution hints.
+    /// Returns true if the constraint has been disabled with
+    /// [CpModelBuilder::disable_constraint]. The enforcement literals
+    /// added by the caller are not considered, even if they are fixed
+    /// to false.
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
-    /// # use cp_sat::proto::CpSolverStatus;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_int_var([(0, 100)]);
-    /// let y = model.new_bool_var();
-    /// model.add_hint(x, 42);
-    /// model.add_hint(y, 1);
-    /// model.del_hints();
-    /// model.add_hint(x, 75);
-    /// model.add_hint(y, 0);
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// let x = model.new_bool_var();
+    /// let constraint = model.add_or([x]);
+    /// assert!(!model.is_constraint_disabled(constraint));
+    /// model.disable_constraint(constraint);
+    /// assert!(model.is_constraint_disabled(constraint));
     /// ```
-    pub fn del_hints(&mut self) {
-        self.proto.solution_hint = None;
+    pub fn is_constraint_disabled(&self, constraint: Constraint) -> bool {
+        self.disabling_literal.is_some_and(|literal| {
+            self.proto.constraints[constraint.0]
+                .enforcement_literal
+                .contains(&literal)
+        })
     }
 
-    /// Sets the minimization objective.
+    /// Adds a boolean OR constraint on a list of [BoolVar].
     ///
     /// # Example
     ///
@@ -654,25 +850,20 @@ impl CpModelBuilder {
     /// # use cp_sat::builder::CpModelBuilder;
     /// # use cp_sat::proto::CpSolverStatus;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_int_var([(0, 100)]);
-    /// model.minimize(x);
+    /// let x = model.new_bool_var();
+    /// let y = model.new_bool_var();
+    /// model.add_or([x, y]);
     /// let response = model.solve();
     /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert_eq!(0, x.solution_value(&response));
-    /// assert_eq!(0., response.objective_value);
+    /// assert!(x.solution_value(&response) || y.solution_value(&response));
     /// ```
-    pub fn minimize<T: Into<LinearExpr>>(&mut self, expr: T) {
-        let expr = expr.into();
-        self.proto.objective = Some(proto::CpObjectiveProto {
-            vars: expr.vars.into_vec(),
-            coeffs: expr.coeffs.into_vec(),
-            offset: expr.constant as f64,
-            scaling_factor: 1.,
-            domain: vec![],
-        });
+    pub fn add_or(&mut self, vars: impl IntoIterator<Item = BoolVar>) -> Constraint {
+        self.add_cst(CstEnum::BoolOr(proto::BoolArgumentProto {
+            literals: vars.into_iter().map(|v| v.0).collect(),
+        }))
     }
 
-    /// Sets the maximization objective.
+    /// Adds a boolean AND constraint on a list of [BoolVar].
     ///
     /// # Example
     ///
@@ -680,45 +871,46 @@ impl CpModelBuilder {
     /// # use cp_sat::builder::CpModelBuilder;
     /// # use cp_sat::proto::CpSolverStatus;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_int_var([(0, 100)]);
-    /// model.maximize(x);
+    /// let x = model.new_bool_var();
+    /// let y = model.new_bool_var();
+    /// model.add_and([x, y]);
     /// let response = model.solve();
     /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert_eq!(100, x.solution_value(&response));
-    /// assert_eq!(100., response.objective_value);
-    /// ```
-    pub fn maximize<T: Into<LinearExpr>>(&mut self, expr: T) {
-        let mut expr = expr.into();
-        for coeff in &mut expr.coeffs {
-            *coeff *= -1;
-        }
-        self.proto.objective = Some(proto::CpObjectiveProto {
-            vars: expr.vars.into_vec(),
-            coeffs: expr.coeffs.into_vec(),
-            offset: -expr.constant as f64,
-            scaling_factor: -1.,
-            domain: vec![],
-        });
+    /// assert!(x.solution_value(&response));
+    /// assert!(y.solution_value(&response));
+    /// ```
+    pub fn add_and(&mut self, vars: impl IntoIterator<Item = BoolVar>) -> Constraint {
+        self.add_cst(CstEnum::BoolAnd(proto::BoolArgumentProto {
+            literals: vars.into_iter().map(|v| v.0).collect(),
+        }))
     }
 
-    /// Returns some statistics on the model.
+    /// Adds a boolean "at most one" constraint on a list of [BoolVar].
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
-    /// let model = CpModelBuilder::default();
-    /// let stats = model.stats();
-    /// assert!(!stats.is_empty());
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let vars: Vec<_> = (0..10).map(|_| model.new_bool_var()).collect();
+    /// model.add_at_most_one(vars.iter().copied());
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(
+    ///     vars.iter()
+    ///         .map(|v| v.solution_value(&response) as u32)
+    ///         .sum::<u32>()
+    ///         <= 1
+    /// );
     /// ```
-    pub fn stats(&self) -> String {
-        ffi::cp_model_stats(self.proto())
+    pub fn add_at_most_one(&mut self, vars: impl IntoIterator<Item = BoolVar>) -> Constraint {
+        self.add_cst(CstEnum::AtMostOne(proto::BoolArgumentProto {
+            literals: vars.into_iter().map(|v| v.0).collect(),
+        }))
     }
 
-    /// Verifies that the given model satisfies all the properties
-    /// described in the proto comments. Returns an empty string if it is
-    /// the case, otherwise fails at the first error and returns a
-    /// human-readable description of the issue.
+    /// Adds a boolean "exactly one" constraint on a list of [BoolVar].
     ///
     /// # Example
     ///
@@ -726,138 +918,2950 @@ impl CpModelBuilder {
     /// # use cp_sat::builder::CpModelBuilder;
     /// # use cp_sat::proto::CpSolverStatus;
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_int_var([(0, -1)]);
-    /// model.maximize(x);
-    /// assert!(!model.validate_cp_model().is_empty());
+    /// let vars: Vec<_> = (0..10).map(|_| model.new_bool_var()).collect();
+    /// model.add_exactly_one(vars.iter().copied());
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(
+    ///     vars.iter()
+    ///         .map(|v| v.solution_value(&response) as u32)
+    ///         .sum::<u32>()
+    ///         == 1
+    /// );
     /// ```
-    pub fn validate_cp_model(&self) -> String {
-        ffi::validate_cp_model(self.proto())
+    pub fn add_exactly_one(&mut self, vars: impl IntoIterator<Item = BoolVar>) -> Constraint {
+        self.add_cst(CstEnum::ExactlyOne(proto::BoolArgumentProto {
+            literals: vars.into_iter().map(|v| v.0).collect(),
+        }))
     }
 
-    /// Solves the model, and returns the corresponding [proto::CpSolverResponse].
+    /// Adds a boolean XOR constraint on a list of [BoolVar].
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
     /// # use cp_sat::proto::CpSolverStatus;
-    /// let model = CpModelBuilder::default();
+    /// let mut model = CpModelBuilder::default();
+    /// let vars: Vec<_> = (0..10).map(|_| model.new_bool_var()).collect();
+    /// model.add_xor(vars.iter().copied());
     /// let response = model.solve();
     /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(
+    ///     vars.iter()
+    ///         .map(|v| v.solution_value(&response) as u32)
+    ///         .sum::<u32>()
+    ///         % 2
+    ///         == 1
+    /// );
     /// ```
-    pub fn solve(&self) -> proto::CpSolverResponse {
-        ffi::solve(self.proto())
+    pub fn add_xor(&mut self, vars: impl IntoIterator<Item = BoolVar>) -> Constraint {
+        self.add_cst(CstEnum::BoolXor(proto::BoolArgumentProto {
+            literals: vars.into_iter().map(|v| v.0).collect(),
+        }))
     }
 
-    /// Solves the model with the given
-    /// [parameters][proto::SatParameters], and returns the
-    /// corresponding [proto::CpSolverResponse].
+    /// Adds the implication `a => b`.
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
-    /// # use cp_sat::proto::{CpSolverStatus, SatParameters};
-    /// let model = CpModelBuilder::default();
-    /// let mut params = SatParameters::default();
-    /// params.max_deterministic_time = Some(1.);
-    /// let response = model.solve_with_parameters(&params);
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_bool_var();
+    /// let y = model.new_bool_var();
+    /// model.add_implication(x, y);
+    /// model.add_and([x]);
+    /// let response = model.solve();
     /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(y.solution_value(&response));
     /// ```
-    pub fn solve_with_parameters(&self, params: &proto::SatParameters) -> proto::CpSolverResponse {
-        ffi::solve_with_parameters(self.proto(), params)
+    pub fn add_implication(&mut self, a: BoolVar, b: BoolVar) -> Constraint {
+        self.add_or([!a, b])
+    }
+
+    /// Adds the equivalence `a <=> b`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_bool_var();
+    /// let y = model.new_bool_var();
+    /// model.add_equivalence(x, !y);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_ne!(x.solution_value(&response), y.solution_value(&response));
+    /// ```
+    pub fn add_equivalence(&mut self, a: BoolVar, b: BoolVar) -> Constraint {
+        self.add_eq(a, b)
+    }
+
+    /// Adds the constraint `target <=> (l1 and l2 and ...)`, and
+    /// returns the two constraints of the encoding, for
+    /// `target => and` and `and => target`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let vars: Vec<_> = (0..3).map(|_| model.new_bool_var()).collect();
+    /// let all = model.new_bool_var();
+    /// model.add_bool_and_eq(all, vars.iter().copied());
+    /// model.add_and([all]);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(vars.iter().all(|v| v.solution_value(&response)));
+    /// ```
+    pub fn add_bool_and_eq(
+        &mut self,
+        target: BoolVar,
+        lits: impl IntoIterator<Item = BoolVar>,
+    ) -> [Constraint; 2] {
+        let lits: Vec<_> = lits.into_iter().collect();
+        let forward = self.add_and(lits.iter().copied());
+        self.proto.constraints[forward.0]
+            .enforcement_literal
+            .push(target.0);
+        let backward = self.add_or(lits.iter().map(|&l| !l).chain(Some(target)));
+        [forward, backward]
+    }
+
+    /// Adds the constraint `target <=> (l1 or l2 or ...)`, and
+    /// returns the two constraints of the encoding, for
+    /// `target => or` and `or => target`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let vars: Vec<_> = (0..3).map(|_| model.new_bool_var()).collect();
+    /// let any = model.new_bool_var();
+    /// model.add_bool_or_eq(any, vars.iter().copied());
+    /// model.add_and([!any]);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(vars.iter().all(|v| !v.solution_value(&response)));
+    /// ```
+    pub fn add_bool_or_eq(
+        &mut self,
+        target: BoolVar,
+        lits: impl IntoIterator<Item = BoolVar>,
+    ) -> [Constraint; 2] {
+        let lits: Vec<_> = lits.into_iter().collect();
+        let forward = self.add_or(lits.iter().copied());
+        self.proto.constraints[forward.0]
+            .enforcement_literal
+            .push(target.0);
+        let backward = self.add_and(lits.iter().map(|&l| !l));
+        self.proto.constraints[backward.0]
+            .enforcement_literal
+            .push((!target).0);
+        [forward, backward]
+    }
+
+    /// Returns a [BoolVar] equivalent to the boolean expression.
+    ///
+    /// The expression is Tseitin-encoded: a new variable is created
+    /// for each distinct `And` or `Or` subexpression, linked to its
+    /// operands by [CpModelBuilder::add_bool_and_eq] or
+    /// [CpModelBuilder::add_bool_or_eq]. Negations do not need new
+    /// variables, and `Implies(a, b)` is encoded as `Or(!a, b)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{BoolExpr, CpModelBuilder};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_bool_var();
+    /// let y = model.new_bool_var();
+    /// let z = model.new_bool_var();
+    /// // b <=> (x and y) or !z
+    /// let expr = BoolExpr::or([BoolExpr::and([x, y]), !BoolExpr::from(z)]);
+    /// let b = model.encode_bool_expr(&expr);
+    /// model.add_and([b, z]);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(x.solution_value(&response) && y.solution_value(&response));
+    /// ```
+    pub fn encode_bool_expr(&mut self, expr: &BoolExpr) -> BoolVar {
+        self.encode_bool_expr_with_cache(expr, &mut HashMap::new())
+    }
+
+    fn encode_bool_expr_with_cache(
+        &mut self,
+        expr: &BoolExpr,
+        cache: &mut HashMap<BoolExpr, BoolVar>,
+    ) -> BoolVar {
+        let (is_and, operands) = match expr {
+            BoolExpr::Var(var) => return *var,
+            BoolExpr::Not(expr) => return !self.encode_bool_expr_with_cache(expr, cache),
+            BoolExpr::Implies(a, b) => {
+                let a = self.encode_bool_expr_with_cache(a, cache);
+                let b = self.encode_bool_expr_with_cache(b, cache);
+                return self.encode_bool_expr_with_cache(
+                    &BoolExpr::or([!BoolExpr::from(a), b.into()]),
+                    cache,
+                );
+            }
+            BoolExpr::And(operands) => (true, operands),
+            BoolExpr::Or(operands) => (false, operands),
+        };
+        if let [operand] = &operands[..] {
+            return self.encode_bool_expr_with_cache(operand, cache);
+        }
+        if let Some(&var) = cache.get(expr) {
+            return var;
+        }
+        let lits: Vec<_> = operands
+            .iter()
+            .map(|e| self.encode_bool_expr_with_cache(e, cache))
+            .collect();
+        let target = self.new_bool_var();
+        if is_and {
+            self.add_bool_and_eq(target, lits);
+        } else {
+            self.add_bool_or_eq(target, lits);
+        }
+        cache.insert(expr.clone(), target);
+        target
+    }
+
+    /// Adds constraints forcing the boolean expression to be true.
+    ///
+    /// Top level `And`, `Or` and `Implies` are directly translated
+    /// into constraints, their operands being encoded as in
+    /// [CpModelBuilder::encode_bool_expr].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{BoolExpr, CpModelBuilder};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_bool_var();
+    /// let y = model.new_bool_var();
+    /// model.add_bool_expr(&BoolExpr::and([
+    ///     BoolExpr::implies(x, y),
+    ///     BoolExpr::or([x, y]),
+    ///     !BoolExpr::from(y) | x,
+    /// ]));
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(x.solution_value(&response) && y.solution_value(&response));
+    /// ```
+    pub fn add_bool_expr(&mut self, expr: &BoolExpr) {
+        let mut cache = HashMap::new();
+        let mut todo = vec![expr];
+        while let Some(expr) = todo.pop() {
+            match expr {
+                BoolExpr::And(operands) => todo.extend(operands),
+                BoolExpr::Or(operands) => {
+                    let lits: Vec<_> = operands
+                        .iter()
+                        .map(|e| self.encode_bool_expr_with_cache(e, &mut cache))
+                        .collect();
+                    self.add_or(lits);
+                }
+                BoolExpr::Implies(a, b) => {
+                    let a = self.encode_bool_expr_with_cache(a, &mut cache);
+                    let b = self.encode_bool_expr_with_cache(b, &mut cache);
+                    self.add_implication(a, b);
+                }
+                _ => {
+                    let lit = self.encode_bool_expr_with_cache(expr, &mut cache);
+                    self.add_and([lit]);
+                }
+            }
+        }
+    }
+
+    /// Adds a "all different" constraint on a list of [BoolVar].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 2)]);
+    /// let y = model.new_int_var([(0, 2)]);
+    /// let z = model.new_int_var([(0, 2)]);
+    /// model.add_all_different([x, y, z]);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// let x_val = x.solution_value(&response);
+    /// let y_val = y.solution_value(&response);
+    /// let z_val = z.solution_value(&response);
+    /// assert!(x_val != y_val);
+    /// assert!(x_val != z_val);
+    /// assert!(y_val != z_val);
+    /// ```
+    pub fn add_all_different(&mut self, vars: impl IntoIterator<Item = IntVar>) -> Constraint {
+        self.add_cst(CstEnum::AllDiff(proto::AllDifferentConstraintProto {
+            vars: vars.into_iter().map(|v| v.0).collect(),
+        }))
+    }
+
+    /// Adds a linear constraint.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 100)]);
+    /// let y = model.new_int_var([(0, 100)]);
+    /// model.add_linear_constraint([(1, x), (3, y)], [(301, i64::MAX)]);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(x.solution_value(&response) + 3 * y.solution_value(&response) >= 301);
+    /// ```
+    pub fn add_linear_constraint(
+        &mut self,
+        expr: impl Into<LinearExpr>,
+        domain: impl IntoIterator<Item = (i64, i64)>,
+    ) -> Constraint {
+        let expr = expr.into();
+        let constant = expr.constant;
+        self.add_cst(CstEnum::Linear(proto::LinearConstraintProto {
+            vars: expr.vars.into_vec(),
+            coeffs: expr.coeffs.into_vec(),
+            domain: domain
+                .into_iter()
+                .flat_map(|(begin, end)| {
+                    [
+                        if begin == i64::MIN {
+                            i64::MIN
+                        } else {
+                            begin.saturating_sub(constant)
+                        },
+                        if end == i64::MAX {
+                            i64::MAX
+                        } else {
+                            end.saturating_sub(constant)
+                        },
+                    ]
+                })
+                .collect(),
+        }))
+    }
+
+    /// Adds an equality constraint between 2 linear expressions.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 50)]);
+    /// let y = model.new_int_var([(53, 100)]);
+    /// model.add_eq(y, LinearExpr::from(x) + 3);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(y.solution_value(&response), x.solution_value(&response) + 3);
+    /// assert_eq!(50, x.solution_value(&response));
+    /// assert_eq!(53, y.solution_value(&response));
+    /// ```
+    pub fn add_eq<T: Into<LinearExpr>, U: Into<LinearExpr>>(
+        &mut self,
+        lhs: T,
+        rhs: U,
+    ) -> Constraint {
+        self.add_linear_constraint(lhs.into() - rhs.into(), [(0, 0)])
+    }
+
+    /// Adds a greater or equal constraint between 2 linear expressions.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 50)]);
+    /// let y = model.new_int_var([(50, 100)]);
+    /// model.add_ge(x, y);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(x.solution_value(&response) >= y.solution_value(&response));
+    /// assert_eq!(50, x.solution_value(&response));
+    /// assert_eq!(50, y.solution_value(&response));
+    /// ```
+    pub fn add_ge<T: Into<LinearExpr>, U: Into<LinearExpr>>(
+        &mut self,
+        lhs: T,
+        rhs: U,
+    ) -> Constraint {
+        self.add_linear_constraint(lhs.into() - rhs.into(), [(0, i64::MAX)])
+    }
+
+    /// Adds a lesser or equal constraint between 2 linear expressions.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(50, 100)]);
+    /// let y = model.new_int_var([(0, 50)]);
+    /// model.add_le(x, y);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(x.solution_value(&response) <= y.solution_value(&response));
+    /// assert_eq!(50, x.solution_value(&response));
+    /// assert_eq!(50, y.solution_value(&response));
+    /// ```
+    pub fn add_le<T: Into<LinearExpr>, U: Into<LinearExpr>>(
+        &mut self,
+        lhs: T,
+        rhs: U,
+    ) -> Constraint {
+        self.add_linear_constraint(lhs.into() - rhs.into(), [(i64::MIN, 0)])
+    }
+
+    /// Adds a stricly greater constraint between 2 linear expressions.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 51)]);
+    /// let y = model.new_int_var([(50, 100)]);
+    /// model.add_gt(x, y);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(x.solution_value(&response) > y.solution_value(&response));
+    /// assert_eq!(51, x.solution_value(&response));
+    /// assert_eq!(50, y.solution_value(&response));
+    /// ```
+    pub fn add_gt<T: Into<LinearExpr>, U: Into<LinearExpr>>(
+        &mut self,
+        lhs: T,
+        rhs: U,
+    ) -> Constraint {
+        self.add_linear_constraint(lhs.into() - rhs.into(), [(1, i64::MAX)])
+    }
+
+    /// Adds a strictly lesser constraint between 2 linear expressions.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(50, 100)]);
+    /// let y = model.new_int_var([(0, 51)]);
+    /// model.add_lt(x, y);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(x.solution_value(&response) < y.solution_value(&response));
+    /// assert_eq!(50, x.solution_value(&response));
+    /// assert_eq!(51, y.solution_value(&response));
+    /// ```
+    pub fn add_lt<T: Into<LinearExpr>, U: Into<LinearExpr>>(
+        &mut self,
+        lhs: T,
+        rhs: U,
+    ) -> Constraint {
+        self.add_linear_constraint(lhs.into() - rhs.into(), [(i64::MIN, -1)])
+    }
+
+    /// Adds a not equal constraint between 2 linear expressions.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_bool_var();
+    /// model.add_ne(x, 1);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(x.solution_value(&response) as i64 != 1);
+    /// assert!(!x.solution_value(&response));
+    /// ```
+    pub fn add_ne<T: Into<LinearExpr>, U: Into<LinearExpr>>(
+        &mut self,
+        lhs: T,
+        rhs: U,
+    ) -> Constraint {
+        self.add_linear_constraint(lhs.into() - rhs.into(), [(i64::MIN, -1), (1, i64::MAX)])
+    }
+
+    /// Adds a constraint that force the `target` to be equal to the
+    /// minimum of the given `exprs`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 10)]);
+    /// let y = model.new_int_var([(5, 15)]);
+    /// let m = model.new_int_var([(-100, 100)]);
+    /// model.add_min_eq(m, [x, y]);
+    /// model.maximize(m);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(10., response.objective_value);
+    /// assert_eq!(10, m.solution_value(&response));
+    /// ```
+    pub fn add_min_eq(
+        &mut self,
+        target: impl Into<LinearExpr>,
+        exprs: impl IntoIterator<Item = impl Into<LinearExpr>>,
+    ) -> Constraint {
+        self.add_cst(CstEnum::LinMin(proto::LinearArgumentProto {
+            target: Some(target.into().into()),
+            exprs: exprs.into_iter().map(|e| e.into().into()).collect(),
+        }))
+    }
+
+    /// Adds a constraint that force the `target` to be equal to the
+    /// maximum of the given `exprs`.
+    ///
+    /// # Exemple
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 10)]);
+    /// let y = model.new_int_var([(5, 15)]);
+    /// let m = model.new_int_var([(-100, 100)]);
+    /// model.add_max_eq(m, [x, y]);
+    /// model.minimize(m);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(5., response.objective_value);
+    /// assert_eq!(5, m.solution_value(&response));
+    /// ```
+    pub fn add_max_eq(
+        &mut self,
+        target: impl Into<LinearExpr>,
+        exprs: impl IntoIterator<Item = impl Into<LinearExpr>>,
+    ) -> Constraint {
+        self.add_cst(CstEnum::LinMax(proto::LinearArgumentProto {
+            target: Some(target.into().into()),
+            exprs: exprs.into_iter().map(|e| e.into().into()).collect(),
+        }))
+    }
+
+    /// Adds an interval constraint `[start, end)` of the given size,
+    /// and returns its identifier, to be used in
+    /// [CpModelBuilder::add_no_overlap].
+    ///
+    /// The bounds and size are [affine views][AffineVar] of
+    /// variables, thus an interval of fixed size can be expressed
+    /// with a single variable. The linear constraints
+    /// `start + size == end` and `size >= 0` are also added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{AffineVar, CpModelBuilder};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let size = model.new_constant(3);
+    /// let starts: Vec<_> = (0..3).map(|_| model.new_int_var([(0, 10)])).collect();
+    /// let intervals: Vec<_> = starts
+    ///     .iter()
+    ///     .map(|&start| model.add_interval(start, size, AffineVar::from(start) + 3))
+    ///     .collect();
+    /// model.add_no_overlap(intervals);
+    /// let makespan = model.new_int_var([(0, 20)]);
+    /// model.add_max_eq(makespan, starts.iter().map(|&s| AffineVar::from(s) + 3));
+    /// model.minimize(makespan);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(9, makespan.solution_value(&response));
+    /// ```
+    pub fn add_interval(
+        &mut self,
+        start: impl Into<AffineVar>,
+        size: impl Into<AffineVar>,
+        end: impl Into<AffineVar>,
+    ) -> Constraint {
+        let (start, size, end) = (start.into(), size.into(), end.into());
+        let interval = self.add_cst(CstEnum::Interval(proto::IntervalConstraintProto {
+            start_view: Some(start.into()),
+            size_view: Some(size.into()),
+            end_view: Some(end.into()),
+            ..Default::default()
+        }));
+        let end = self.add_eq(LinearExpr::from(start) + size, end);
+        let size = self.add_ge(size, 0);
+        self.interval_linears.insert(interval.0, [end.0, size.0]);
+        interval
+    }
+
+    /// Adds a constraint that forces the given
+    /// [intervals][CpModelBuilder::add_interval] to be disjoint.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{AffineVar, CpModelBuilder};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 4)]);
+    /// let y = model.new_int_var([(0, 4)]);
+    /// let two = model.new_constant(2);
+    /// let i = model.add_interval(x, two, AffineVar::from(x) + 2);
+    /// let j = model.add_interval(y, two, AffineVar::from(y) + 2);
+    /// model.add_no_overlap([i, j]);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!((x.solution_value(&response) - y.solution_value(&response)).abs() >= 2);
+    /// ```
+    pub fn add_no_overlap(
+        &mut self,
+        intervals: impl IntoIterator<Item = Constraint>,
+    ) -> Constraint {
+        self.add_cst(CstEnum::NoOverlap(proto::NoOverlapConstraintProto {
+            intervals: intervals.into_iter().map(|i| i.0 as i32).collect(),
+        }))
+    }
+
+    /// Adds a cumulative constraint: at any time, the sum of the
+    /// demands of the [intervals][CpModelBuilder::add_interval]
+    /// containing this time is at most the capacity.
+    ///
+    /// The demands and the capacity are variables, as required by the
+    /// CP SAT protobuf. A constant demand can be given with
+    /// [CpModelBuilder::new_constant], and an
+    /// [affine demand][AffineVar] needs an auxiliary variable equal to
+    /// it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{AffineVar, CpModelBuilder};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let size = model.new_constant(2);
+    /// let starts: Vec<_> = (0..3).map(|_| model.new_int_var([(0, 10)])).collect();
+    /// let intervals: Vec<_> = starts
+    ///     .iter()
+    ///     .map(|&start| model.add_interval(start, size, AffineVar::from(start) + 2))
+    ///     .collect();
+    /// // demands of 1, 1 and 2, with a capacity of 2
+    /// let demands = [model.new_constant(1), model.new_constant(1), model.new_constant(2)];
+    /// let capacity = model.new_constant(2);
+    /// model.add_cumulative(intervals, demands, capacity);
+    /// let makespan = model.new_int_var([(0, 20)]);
+    /// model.add_max_eq(makespan, starts.iter().map(|&s| AffineVar::from(s) + 2));
+    /// model.minimize(makespan);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(4, makespan.solution_value(&response));
+    /// ```
+    pub fn add_cumulative(
+        &mut self,
+        intervals: impl IntoIterator<Item = Constraint>,
+        demands: impl IntoIterator<Item = IntVar>,
+        capacity: IntVar,
+    ) -> Constraint {
+        self.add_cst(CstEnum::Cumulative(proto::CumulativeConstraintProto {
+            capacity: capacity.0,
+            intervals: intervals.into_iter().map(|i| i.0 as i32).collect(),
+            demands: demands.into_iter().map(|v| v.0).collect(),
+        }))
+    }
+
+    pub(crate) fn add_cst(&mut self, cst: CstEnum) -> Constraint {
+        let index = self.proto.constraints.len();
+        self.proto.constraints.push(proto::ConstraintProto {
+            constraint: Some(cst),
+            ..Default::default()
+        });
+        Constraint(index)
+    }
+
+    /// Add a solution hint.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 100)]);
+    /// let y = model.new_bool_var();
+    /// model.add_hint(x, 42);
+    /// model.add_hint(y, 1);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// ```
+    pub fn add_hint(&mut self, var: impl Into<IntVar>, value: i64) {
+        let var = var.into();
+        let hints = self
+            .proto
+            .solution_hint
+            .get_or_insert_with(Default::default);
+        if var.0 < 0 {
+            hints.vars.push(var.not().0);
+            hints.values.push(1 - value);
+        } else {
+            hints.vars.push(var.0);
+            hints.values.push(value);
+        }
+    }
+
+    /// Delete all solution hints.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 100)]);
+    /// let y = model.new_bool_var();
+    /// model.add_hint(x, 42);
+    /// model.add_hint(y, 1);
+    /// model.del_hints();
+    /// model.add_hint(x, 75);
+    /// model.add_hint(y, 0);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// ```
+    pub fn del_hints(&mut self) {
+        self.proto.solution_hint = None;
+    }
+
+    /// Replaces the solution hints by the solution of the response,
+    /// typically to warm-start a modified model from the solution of
+    /// the original model. See [CpModelBuilder::hint_from_solution].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 100)]);
+    /// let y = model.new_int_var([(0, 100)]);
+    /// model.add_le([(1, x), (1, y)], 100);
+    /// model.maximize([(2, x), (1, y)]);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    ///
+    /// model.add_le(x, 90);
+    /// model.hint_from_response(&response);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(90, x.solution_value(&response));
+    /// ```
+    pub fn hint_from_response(&mut self, response: &proto::CpSolverResponse) {
+        self.hint_from_solution(&response.solution);
+    }
+
+    /// Replaces the solution hints by the given solution, i.e. the
+    /// value of each variable in order of creation, as in the
+    /// `solution` field of a [proto::CpSolverResponse].
+    ///
+    /// The solution may be shorter than the number of variables, for
+    /// instance if variables were created after the solve: only the
+    /// variables with a value are hinted. An empty solution removes
+    /// the hints.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 100)]);
+    /// let y = model.new_int_var([(0, 100)]);
+    /// model.add_hint(y, 5);
+    /// model.hint_from_solution(&[42]);
+    /// let hint = model.proto().solution_hint.as_ref().unwrap();
+    /// assert_eq!(hint.vars, [0]);
+    /// assert_eq!(hint.values, [42]);
+    /// ```
+    pub fn hint_from_solution(&mut self, solution: &[i64]) {
+        let len = solution.len().min(self.proto.variables.len());
+        self.proto.solution_hint = if len == 0 {
+            None
+        } else {
+            Some(proto::PartialVariableAssignment {
+                vars: (0..len as i32).collect(),
+                values: solution[..len].to_vec(),
+            })
+        };
+    }
+
+    /// Verifies the solution hints, and returns the variables hinted
+    /// several times and the values outside the domain of their
+    /// variable. See [validation::validate_hints].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::validation::ValidationErrorKind;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 10)]);
+    /// model.add_hint(x, 11);
+    /// let errors = model.validate_hints();
+    /// assert_eq!(1, errors.len());
+    /// assert_eq!(ValidationErrorKind::HintOutOfDomain, errors[0].kind);
+    /// ```
+    pub fn validate_hints(&self) -> Vec<validation::ValidationError> {
+        validation::validate_hints(self.proto())
+    }
+
+    /// Adds a decision strategy: the solver branches on the given
+    /// variables, choosing the next one with `variable_selection`,
+    /// and restricting its domain with `domain_reduction`.
+    ///
+    /// The strategies are followed in order of addition, but only
+    /// strictly with the parameters of [fixed_search_parameters].
+    /// Otherwise, they are only used as heuristics by some workers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{fixed_search_parameters, CpModelBuilder, DomainReduction, VariableSelection};
+    /// # use cp_sat::proto::{CpSolverStatus, SatParameters};
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 10)]);
+    /// let y = model.new_int_var([(0, 10)]);
+    /// model.add_decision_strategy([x, y], VariableSelection::ChooseFirst, DomainReduction::SelectMaxValue);
+    /// let params = fixed_search_parameters(&SatParameters::default());
+    /// let response = model.solve_with_parameters(&params);
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(10, x.solution_value(&response));
+    /// assert_eq!(10, y.solution_value(&response));
+    /// ```
+    pub fn add_decision_strategy(
+        &mut self,
+        vars: impl IntoIterator<Item = impl Into<IntVar>>,
+        variable_selection: VariableSelection,
+        domain_reduction: DomainReduction,
+    ) {
+        self.proto
+            .search_strategy
+            .push(proto::DecisionStrategyProto {
+                variables: vars.into_iter().map(|v| v.into().0).collect(),
+                variable_selection_strategy:
+                    proto::decision_strategy_proto::VariableSelectionStrategy::from(
+                        variable_selection,
+                    ) as i32,
+                domain_reduction_strategy:
+                    proto::decision_strategy_proto::DomainReductionStrategy::from(domain_reduction)
+                        as i32,
+                transformations: vec![],
+            });
+    }
+
+    /// Delete all decision strategies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, DomainReduction, VariableSelection};
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_bool_var();
+    /// model.add_decision_strategy([x], VariableSelection::ChooseFirst, DomainReduction::SelectMinValue);
+    /// assert_eq!(1, model.proto().search_strategy.len());
+    /// model.del_decision_strategies();
+    /// assert!(model.proto().search_strategy.is_empty());
+    /// ```
+    pub fn del_decision_strategies(&mut self) {
+        self.proto.search_strategy.clear();
+    }
+
+    /// Sets the minimization objective.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 100)]);
+    /// model.minimize(x);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(0, x.solution_value(&response));
+    /// assert_eq!(0., response.objective_value);
+    /// ```
+    pub fn minimize<T: Into<LinearExpr>>(&mut self, expr: T) {
+        let expr = expr.into();
+        self.proto.objective = Some(proto::CpObjectiveProto {
+            vars: expr.vars.into_vec(),
+            coeffs: expr.coeffs.into_vec(),
+            offset: expr.constant as f64,
+            scaling_factor: 1.,
+            domain: vec![],
+        });
+    }
+
+    /// Sets the maximization objective.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 100)]);
+    /// model.maximize(x);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(100, x.solution_value(&response));
+    /// assert_eq!(100., response.objective_value);
+    /// ```
+    pub fn maximize<T: Into<LinearExpr>>(&mut self, expr: T) {
+        let mut expr = expr.into();
+        for coeff in &mut expr.coeffs {
+            *coeff *= -1;
+        }
+        self.proto.objective = Some(proto::CpObjectiveProto {
+            vars: expr.vars.into_vec(),
+            coeffs: expr.coeffs.into_vec(),
+            offset: -expr.constant as f64,
+            scaling_factor: -1.,
+            domain: vec![],
+        });
+    }
+
+    /// Returns the objective expression, as given to
+    /// [CpModelBuilder::minimize] or [CpModelBuilder::maximize], or
+    /// `None` if the model has no objective.
+    ///
+    /// With a [scaling factor][CpModelBuilder::set_objective_scaling]
+    /// other than ±1, the reported objective value is the expression
+    /// multiplied by the absolute value of the scaling factor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 100)]);
+    /// let y = model.new_int_var([(0, 100)]);
+    /// model.maximize([(2, x), (3, y)]);
+    /// assert!(model.is_maximization());
+    ///
+    /// // apply a cutoff from a previous run
+    /// let objective = model.objective().unwrap();
+    /// model.add_ge(objective, 250);
+    /// ```
+    pub fn objective(&self) -> Option<LinearExpr> {
+        let objective = self.proto.objective.as_ref()?;
+        let sign = if objective.scaling_factor < 0. { -1 } else { 1 };
+        Some(LinearExpr {
+            vars: objective.vars.iter().copied().collect(),
+            coeffs: objective.coeffs.iter().map(|&c| sign * c).collect(),
+            constant: sign * objective.offset.round() as i64,
+        })
+    }
+
+    /// Returns true if the objective is a maximization, i.e. if its
+    /// scaling factor is negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 100)]);
+    /// assert!(!model.is_maximization());
+    /// model.maximize(x);
+    /// assert!(model.is_maximization());
+    /// model.minimize(x);
+    /// assert!(!model.is_maximization());
+    /// ```
+    pub fn is_maximization(&self) -> bool {
+        self.proto
+            .objective
+            .as_ref()
+            .is_some_and(|o| o.scaling_factor < 0.)
+    }
+
+    /// Restricts the values of the objective, as reported by
+    /// `objective_value` in the response, to the given domain.
+    ///
+    /// An empty domain removes the restriction. The bounds are
+    /// rounded inwards when the scaling factor and offset of the
+    /// objective do not map them to integers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the model has no objective.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 100)]);
+    /// model.minimize(x);
+    /// // only accept a cost of at least 10 and at most 1000
+    /// model.set_objective_domain([(10, 1000)]);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(10, x.solution_value(&response));
+    /// ```
+    #[track_caller]
+    pub fn set_objective_domain(&mut self, domain: impl IntoIterator<Item = (i64, i64)>) {
+        let objective = self
+            .proto
+            .objective
+            .as_mut()
+            .expect("the model has no objective");
+        // a scaling factor of 0 means 1 in the proto
+        let scaling_factor = if objective.scaling_factor == 0. {
+            1.
+        } else {
+            objective.scaling_factor
+        };
+        let offset = objective.offset;
+        let to_internal = |value: i64| {
+            if value == i64::MIN || value == i64::MAX {
+                let infinite = (value == i64::MAX) == (scaling_factor > 0.);
+                if infinite {
+                    f64::INFINITY
+                } else {
+                    f64::NEG_INFINITY
+                }
+            } else {
+                value as f64 / scaling_factor - offset
+            }
+        };
+        let mut intervals: Vec<(i64, i64)> = domain
+            .into_iter()
+            .filter_map(|(begin, end)| {
+                let (begin, end) = (to_internal(begin), to_internal(end));
+                let (begin, end) = if begin <= end {
+                    (begin, end)
+                } else {
+                    (end, begin)
+                };
+                let (begin, end) = (begin.ceil() as i64, end.floor() as i64);
+                (begin <= end).then_some((begin, end))
+            })
+            .collect();
+        intervals.sort_unstable();
+        // the domain must be made of disjoint and non-adjacent intervals
+        let mut merged: Vec<(i64, i64)> = Vec::with_capacity(intervals.len());
+        for (begin, end) in intervals {
+            match merged.last_mut() {
+                Some(last) if begin <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+                _ => merged.push((begin, end)),
+            }
+        }
+        objective.domain = merged.into_iter().flat_map(|(b, e)| [b, e]).collect();
+    }
+
+    /// Sets the scaling factor and offset of the objective.
+    ///
+    /// The solver minimizes the objective expression, and reports
+    /// `scaling_factor * (expression + offset)` as the objective
+    /// value. A negative scaling factor thus means a maximization.
+    /// The [objective domain][CpModelBuilder::set_objective_domain]
+    /// is kept on the expression, and should be set after.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the model has no objective.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let cents = model.new_int_var([(150, 1000)]);
+    /// model.minimize(cents);
+    /// // report the objective in euros
+    /// model.set_objective_scaling(0.01, 0.);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(1.5, response.objective_value);
+    /// ```
+    #[track_caller]
+    pub fn set_objective_scaling(&mut self, scaling_factor: f64, offset: f64) {
+        let objective = self
+            .proto
+            .objective
+            .as_mut()
+            .expect("the model has no objective");
+        objective.scaling_factor = scaling_factor;
+        objective.offset = offset;
+    }
+
+    /// Sets the minimization objective with floating point
+    /// coefficients, and returns the scaling error.
+    ///
+    /// The coefficients are multiplied by the smallest power of 10,
+    /// up to `10^max_scaling_digits`, that makes them integers, or
+    /// else by the largest one and rounded. The `scaling_factor` of
+    /// the objective undoes this multiplication, thus
+    /// `objective_value` in the response is in the units of the
+    /// expression. The returned scaling error is the largest absolute
+    /// difference between a coefficient and its rounded value, 0 if
+    /// the scaling is exact.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, FloatLinearExpr};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(1, 10)]);
+    /// let y = model.new_int_var([(1, 10)]);
+    /// let error = model.minimize_float([(0.25, x), (1.5, y)], 6);
+    /// assert_eq!(error, 0.);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(1.75, response.objective_value);
+    /// ```
+    pub fn minimize_float(
+        &mut self,
+        expr: impl Into<FloatLinearExpr>,
+        max_scaling_digits: u32,
+    ) -> f64 {
+        self.set_float_objective(expr.into(), max_scaling_digits, 1.)
+    }
+
+    /// Sets the maximization objective with floating point
+    /// coefficients, and returns the scaling error. See
+    /// [CpModelBuilder::minimize_float] for the scaling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, FloatLinearExpr};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 10)]);
+    /// let error = model.maximize_float(FloatLinearExpr::from((1. / 3., x)) + 0.5, 2);
+    /// assert!(error > 0. && error < 0.01);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!((response.objective_value - 3.8).abs() < 1e-9);
+    /// ```
+    pub fn maximize_float(
+        &mut self,
+        expr: impl Into<FloatLinearExpr>,
+        max_scaling_digits: u32,
+    ) -> f64 {
+        self.set_float_objective(expr.into(), max_scaling_digits, -1.)
+    }
+
+    fn set_float_objective(&mut self, expr: FloatLinearExpr, max_digits: u32, sign: f64) -> f64 {
+        let multiplier = integer_multiplier(&expr.coeffs, max_digits).unwrap_or_else(|| {
+            (0..=max_digits)
+                .rev()
+                .map(|d| 10f64.powi(d as i32))
+                .find(|&m| expr.coeffs.iter().all(|&c| (c * m).abs() < 2f64.powi(53)))
+                .unwrap_or(1.)
+        });
+        let coeffs: Vec<i64> = expr
+            .coeffs
+            .iter()
+            .map(|&c| (c * multiplier).round() as i64)
+            .collect();
+        let error = expr
+            .coeffs
+            .iter()
+            .zip(&coeffs)
+            .map(|(&c, &scaled)| (scaled as f64 / multiplier - c).abs())
+            .fold(0., f64::max);
+        self.proto.objective = Some(proto::CpObjectiveProto {
+            vars: expr.vars.into_vec(),
+            coeffs: coeffs.into_iter().map(|c| sign as i64 * c).collect(),
+            offset: sign * expr.constant * multiplier,
+            scaling_factor: sign / multiplier,
+            domain: vec![],
+        });
+        error
+    }
+
+    /// Appends the variables and constraints of another model to this
+    /// model, and returns the [VarMap] translating the variables and
+    /// constraints of `other` to this model.
+    ///
+    /// The solution hints, decision strategies and assumptions of
+    /// `other` are also appended. Its objective is ignored: the
+    /// objective of the composed model has to be set explicitly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a name of `other` is already used in the [strict
+    /// naming mode][CpModelBuilder::set_strict_names]. See
+    /// [CpModelBuilder::try_merge] for a fallible version.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// fn line(capacity: i64) -> (CpModelBuilder, cp_sat::builder::IntVar) {
+    ///     let mut model = CpModelBuilder::default();
+    ///     let load = model.new_int_var_with_name([(0, 100)], "load");
+    ///     model.add_le(load, capacity);
+    ///     (model, load)
+    /// }
+    ///
+    /// let mut plant = CpModelBuilder::default();
+    /// let mut loads = Vec::new();
+    /// for capacity in [10, 20] {
+    ///     let (line, load) = line(capacity);
+    ///     let map = plant.merge(&line);
+    ///     loads.push(map.var(load));
+    /// }
+    /// plant.add_eq(loads.iter().copied().collect::<cp_sat::builder::LinearExpr>(), 25);
+    /// assert_eq!(plant.proto().variables.len(), 2);
+    /// assert_eq!(plant.proto().constraints.len(), 3);
+    /// assert_eq!(plant.var_name(loads[1]), "load");
+    /// ```
+    #[track_caller]
+    pub fn merge(&mut self, other: &CpModelBuilder) -> VarMap {
+        match self.try_merge(other) {
+            Ok(map) => map,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Same as [CpModelBuilder::merge], but returns an error, and
+    /// leaves this model unchanged, if a variable or constraint of
+    /// `other` would get a name already used in the [strict naming
+    /// mode][CpModelBuilder::set_strict_names].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// let mut model = CpModelBuilder::default();
+    /// model.new_bool_var_with_name("x");
+    /// model.set_strict_names(true).unwrap();
+    /// let mut other = CpModelBuilder::default();
+    /// other.new_bool_var_with_name("y");
+    /// other.new_bool_var_with_name("x");
+    /// let error = model.try_merge(&other).unwrap_err();
+    /// assert_eq!(error.to_string(), "variable #2 (x): name already used by variable #0");
+    /// assert_eq!(model.proto().variables.len(), 1);
+    /// ```
+    pub fn try_merge(&mut self, other: &CpModelBuilder) -> Result<VarMap, DuplicateNameError> {
+        let map = VarMap {
+            var_offset: self.proto.variables.len() as i32,
+            constraint_offset: self.proto.constraints.len(),
+        };
+        if self.strict_names {
+            self.check_merged_names(&other.proto, &map)?;
+        }
+        // the constraints disabled in the other model stay disabled
+        let disabling_literals = other.disabling_literal.map(|literal| {
+            let literal = map.var_ref(literal);
+            (literal, *self.disabling_literal.get_or_insert(literal))
+        });
+        let other_intervals = &other.interval_linears;
+        let other = &other.proto;
+        for var in &other.variables {
+            self.proto.variables.push(proto::IntegerVariableProto {
+                name: String::new(),
+                domain: var.domain.clone(),
+            });
+            self.rename_var(self.proto.variables.len() - 1, var.name.clone());
+        }
+        for cst in &other.constraints {
+            let mut cst = cst.clone();
+            let name = std::mem::take(&mut cst.name);
+            refs::visit_vars_mut(&mut cst, &mut |v| *v = map.var_ref(*v));
+            refs::visit_intervals_mut(&mut cst, &mut |i| *i += map.constraint_offset as i32);
+            if let Some((other_literal, literal)) = disabling_literals {
+                for l in &mut cst.enforcement_literal {
+                    if *l == other_literal {
+                        *l = literal;
+                    }
+                }
+            }
+            self.proto.constraints.push(cst);
+            self.rename_constraint(self.proto.constraints.len() - 1, name);
+        }
+        self.interval_linears
+            .extend(other_intervals.iter().map(|(&interval, linears)| {
+                (
+                    interval + map.constraint_offset,
+                    linears.map(|c| c + map.constraint_offset),
+                )
+            }));
+        if let Some(hint) = &other.solution_hint {
+            let hints = self
+                .proto
+                .solution_hint
+                .get_or_insert_with(Default::default);
+            hints.vars.extend(hint.vars.iter().map(|&v| map.var_ref(v)));
+            hints.values.extend_from_slice(&hint.values);
+        }
+        for strategy in &other.search_strategy {
+            let mut strategy = strategy.clone();
+            for var in &mut strategy.variables {
+                *var = map.var_ref(*var);
+            }
+            self.proto.search_strategy.push(strategy);
+        }
+        self.proto
+            .assumptions
+            .extend(other.assumptions.iter().map(|&v| map.var_ref(v)));
+        Ok(map)
+    }
+
+    /// Checks that merging `other` does not duplicate any name, among
+    /// the names of this model and the names of `other`.
+    fn check_merged_names(
+        &self,
+        other: &proto::CpModelProto,
+        map: &VarMap,
+    ) -> Result<(), DuplicateNameError> {
+        fn find_duplicate<'a>(
+            existing: &HashMap<String, Vec<usize>>,
+            names: impl Iterator<Item = &'a str>,
+            offset: usize,
+        ) -> Option<(usize, usize, &'a str)> {
+            let mut merged = HashMap::new();
+            for (index, name) in names.enumerate().filter(|(_, n)| !n.is_empty()) {
+                let index = index + offset;
+                let first = existing.get(name).map(|indices| indices[0]);
+                if let Some(first) = first.or_else(|| merged.get(name).copied()) {
+                    return Some((index, first, name));
+                }
+                merged.insert(name, index);
+            }
+            None
+        }
+        let names = self.names();
+        let vars = other.variables.iter().map(|v| v.name.as_str());
+        if let Some((index, first, name)) =
+            find_duplicate(&names.vars, vars, map.var_offset as usize)
+        {
+            let location = |index| Location::Variable {
+                index,
+                name: name.to_string(),
+            };
+            return Err(DuplicateNameError {
+                location: location(index),
+                first: location(first),
+            });
+        }
+        let constraints = other.constraints.iter().map(|c| c.name.as_str());
+        if let Some((index, first, name)) =
+            find_duplicate(&names.constraints, constraints, map.constraint_offset)
+        {
+            let location = |index| Location::Constraint {
+                index,
+                name: name.to_string(),
+            };
+            return Err(DuplicateNameError {
+                location: location(index),
+                first: location(first),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns a sub-model with only the given constraints, the
+    /// interval constraints they use, and the linear constraints added
+    /// along these intervals by [CpModelBuilder::add_interval].
+    ///
+    /// All the variables are kept, thus the variable handles of this
+    /// model are valid in the sub-model, as well as its objective,
+    /// solution hints, decision strategies and assumptions. The kept
+    /// constraints are renumbered, in their original order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_bool_var();
+    /// let y = model.new_bool_var();
+    /// let c1 = model.add_or([x, y]);
+    /// let _c2 = model.add_and([!x]);
+    /// let c3 = model.add_and([!y]);
+    /// let sub_model = model.extract([c3, c1]);
+    /// assert_eq!(sub_model.proto().variables.len(), 2);
+    /// assert_eq!(sub_model.proto().constraints.len(), 2);
+    /// assert_eq!(sub_model.proto().constraints[1], model.proto().constraints[2]);
+    /// ```
+    pub fn extract(&self, constraints: impl IntoIterator<Item = Constraint>) -> CpModelBuilder {
+        let mut kept = vec![false; self.proto.constraints.len()];
+        let mut keep = |index: usize| {
+            if let Some(kept) = kept.get_mut(index) {
+                *kept = true;
+            }
+            for &linear in self.interval_linears.get(&index).into_iter().flatten() {
+                kept[linear] = true;
+            }
+        };
+        for cst in constraints {
+            keep(cst.0);
+            for interval in refs::intervals(&self.proto.constraints[cst.0]) {
+                keep(interval);
+            }
+        }
+        let mut new_index = vec![0; kept.len()];
+        let mut num_kept = 0;
+        for (index, &kept) in kept.iter().enumerate() {
+            new_index[index] = num_kept as i32;
+            num_kept += kept as usize;
+        }
+        let mut proto = proto::CpModelProto {
+            constraints: vec![],
+            ..self.proto.clone()
+        };
+        for (cst, _) in self.proto.constraints.iter().zip(&kept).filter(|(_, &k)| k) {
+            let mut cst = cst.clone();
+            refs::visit_intervals_mut(&mut cst, &mut |i| {
+                if let Some(&index) = new_index.get(*i as usize) {
+                    *i = index;
+                }
+            });
+            proto.constraints.push(cst);
+        }
+        let interval_linears = self
+            .interval_linears
+            .iter()
+            .filter(|(&interval, _)| kept[interval])
+            .map(|(&interval, linears)| {
+                (
+                    new_index[interval] as usize,
+                    linears.map(|c| new_index[c] as usize),
+                )
+            })
+            .collect();
+        Self {
+            disabling_literal: self.disabling_literal,
+            interval_linears,
+            ..proto.into()
+        }
+    }
+
+    /// Returns some statistics on the model.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// let model = CpModelBuilder::default();
+    /// let stats = model.stats();
+    /// assert!(!stats.is_empty());
+    /// ```
+    pub fn stats(&self) -> String {
+        ffi::cp_model_stats(self.proto())
+    }
+
+    /// Returns structured statistics on the model, computed without
+    /// calling CP SAT.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_bool_var();
+    /// model.add_or([x]);
+    /// let stats = model.model_stats();
+    /// assert_eq!(1, stats.num_variables);
+    /// assert_eq!(1, stats.num_constraints);
+    /// assert_eq!(None, stats.num_objective_terms);
+    /// ```
+    pub fn model_stats(&self) -> ModelStats {
+        ModelStats::from_proto(self.proto())
+    }
+
+    /// Verifies that the given model satisfies all the properties
+    /// described in the proto comments. Returns an empty string if it is
+    /// the case, otherwise fails at the first error and returns a
+    /// human-readable description of the issue.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, -1)]);
+    /// model.maximize(x);
+    /// assert!(!model.validate_cp_model().is_empty());
+    /// ```
+    pub fn validate_cp_model(&self) -> String {
+        ffi::validate_cp_model(self.proto())
+    }
+
+    /// Verifies that the model satisfies all the properties described
+    /// in the proto comments, without calling CP SAT. Returns all the
+    /// problems found, an empty vector meaning that the model is
+    /// valid. See [validation::validate].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::validation::ValidationErrorKind;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, -1)]);
+    /// let y = model.new_int_var([]);
+    /// model.maximize(x);
+    /// let errors = model.validate();
+    /// assert_eq!(2, errors.len());
+    /// assert_eq!(ValidationErrorKind::InvalidDomain, errors[0].kind);
+    /// assert_eq!(ValidationErrorKind::EmptyDomain, errors[1].kind);
+    /// ```
+    pub fn validate(&self) -> Vec<validation::ValidationError> {
+        validation::validate(self.proto())
+    }
+
+    /// Looks for common modelling mistakes with the default
+    /// [options][lint::LintOptions]. See [lint::lint].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::lint::{LintKind, Severity};
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(i64::MIN, i64::MAX)]);
+    /// let b = model.new_bool_var();
+    /// model.add_le([(1, x), (10_000_000, b.into())], 10_000_000);
+    /// let lints = model.lint();
+    /// assert_eq!(LintKind::HugeDomain, lints[0].kind);
+    /// assert_eq!(Severity::Error, lints[0].severity);
+    /// assert_eq!(LintKind::BigMCoefficient, lints[1].kind);
+    /// // can be used as a test gate
+    /// assert!(lints.iter().any(|l| l.severity >= Severity::Warning));
+    /// ```
+    pub fn lint(&self) -> Vec<lint::Lint> {
+        self.lint_with_options(&Default::default())
+    }
+
+    /// Looks for common modelling mistakes with the given
+    /// [options][lint::LintOptions]. See [lint::lint].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::lint::LintOptions;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 1000)]);
+    /// model.add_le([(500, x)], 1000);
+    /// assert!(model.lint().is_empty());
+    /// let options = LintOptions {
+    ///     max_coefficient: 100,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(1, model.lint_with_options(&options).len());
+    /// ```
+    pub fn lint_with_options(&self, options: &lint::LintOptions) -> Vec<lint::Lint> {
+        lint::lint(self.proto(), options)
+    }
+
+    /// Returns the variable-constraint graph of the model in the
+    /// GraphViz DOT format, with the default
+    /// [options][dot::DotOptions]. See [dot::to_dot].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_bool_var_with_name("x");
+    /// let y = model.new_bool_var_with_name("y");
+    /// model.add_or([x, !y]);
+    /// assert_eq!(
+    ///     model.to_dot(),
+    ///     "graph model {
+    ///   node [style=filled, fillcolor=white];
+    ///   v0 [label=\"x\"];
+    ///   v1 [label=\"y\"];
+    ///   c0 [label=\"#0\\nbool_or\", shape=box, fillcolor=lightblue];
+    ///   c0 -- v0;
+    ///   c0 -- v1;
+    /// }
+    /// "
+    /// );
+    /// ```
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with_options(&Default::default())
+    }
+
+    /// Returns the variable-constraint graph of the model in the
+    /// GraphViz DOT format, with the given
+    /// [options][dot::DotOptions]. See [dot::to_dot].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::dot::DotOptions;
+    /// let mut model = CpModelBuilder::default();
+    /// let vars: Vec<_> = (0..100).map(|_| model.new_bool_var()).collect();
+    /// model.add_le(vars.iter().copied().collect::<cp_sat::builder::LinearExpr>(), 10);
+    /// let options = DotOptions {
+    ///     max_linear_size: Some(10),
+    ///     ..Default::default()
+    /// };
+    /// let dot = model.to_dot_with_options(&options);
+    /// assert!(dot.contains("linear (100 variables)"));
+    /// assert!(!dot.contains(" -- "));
+    /// ```
+    pub fn to_dot_with_options(&self, options: &dot::DotOptions) -> String {
+        dot::to_dot(self.proto(), options)
+    }
+
+    /// Solves the model, and returns the corresponding [proto::CpSolverResponse].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let model = CpModelBuilder::default();
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// ```
+    pub fn solve(&self) -> proto::CpSolverResponse {
+        ffi::solve(self.proto())
+    }
+
+    /// Solves the model with the given
+    /// [parameters][proto::SatParameters], and returns the
+    /// corresponding [proto::CpSolverResponse].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::{CpSolverStatus, SatParameters};
+    /// let model = CpModelBuilder::default();
+    /// let mut params = SatParameters::default();
+    /// params.max_deterministic_time = Some(1.);
+    /// let response = model.solve_with_parameters(&params);
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// ```
+    pub fn solve_with_parameters(&self, params: &proto::SatParameters) -> proto::CpSolverResponse {
+        ffi::solve_with_parameters(self.proto(), params)
+    }
+
+    /// Solves the model, using the solution of the previous call to
+    /// this method, if any, as [hint][CpModelBuilder::hint_from_solution].
+    ///
+    /// This is useful to resolve quickly the model after some edits,
+    /// e.g. with [CpModelBuilder::tighten_var_domain] or
+    /// [CpModelBuilder::disable_constraint]. The previous solution
+    /// replaces the hints of the model, and is kept until a solve
+    /// finds a new solution.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 10)]);
+    /// let y = model.new_int_var([(0, 10)]);
+    /// model.add_le([(1, x), (1, y)], 15);
+    /// model.maximize([(2, x), (1, y)]);
+    /// let response = model.resolve();
+    /// assert_eq!(10, x.solution_value(&response));
+    ///
+    /// // what if x was at most 8?
+    /// model.tighten_var_domain(x, [(0, 8)]);
+    /// let response = model.resolve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(8, x.solution_value(&response));
+    /// assert_eq!(7, y.solution_value(&response));
+    /// ```
+    pub fn resolve(&mut self) -> proto::CpSolverResponse {
+        self.resolve_with_parameters(&proto::SatParameters::default())
+    }
+
+    /// Same as [CpModelBuilder::resolve], with the given
+    /// [parameters][proto::SatParameters].
+    pub fn resolve_with_parameters(
+        &mut self,
+        params: &proto::SatParameters,
+    ) -> proto::CpSolverResponse {
+        if !self.last_solution.is_empty() {
+            let solution = self.last_solution.clone();
+            self.hint_from_solution(&solution);
+        }
+        let response = self.solve_with_parameters(params);
+        if !response.solution.is_empty() {
+            self.last_solution = response.solution.clone();
+        }
+        response
+    }
+
+    /// Optimizes several objectives in priority order, and returns
+    /// the response of each stage.
+    ///
+    /// Each stage optimizes one objective with the given
+    /// [parameters][proto::SatParameters], then constrains this
+    /// objective to stay within its
+    /// [tolerance][LexicographicObjective::with_tolerance] of the
+    /// found value during the next stages. The solution of a stage is
+    /// used as the hint of the next one. The model itself is not
+    /// modified: its objective and hints are ignored.
+    ///
+    /// The solving stops at the first stage that does not find a
+    /// solution, thus the last response is the one of the last stage
+    /// that was run. When every objective has been solved, the last
+    /// response contains the lexicographically best solution that
+    /// was found.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, LexicographicObjective};
+    /// # use cp_sat::proto::{CpSolverStatus, SatParameters};
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 10)]);
+    /// let y = model.new_int_var([(0, 10)]);
+    /// model.add_le([(1, x), (1, y)], 12);
+    /// let responses = model.solve_lexicographic(
+    ///     [
+    ///         LexicographicObjective::maximize(x),
+    ///         LexicographicObjective::maximize(y),
+    ///     ],
+    ///     &SatParameters::default(),
+    /// );
+    /// assert_eq!(responses.len(), 2);
+    /// let last = responses.last().unwrap();
+    /// assert_eq!(last.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(x.solution_value(last), 10);
+    /// assert_eq!(y.solution_value(last), 2);
+    /// ```
+    pub fn solve_lexicographic(
+        &self,
+        objectives: impl IntoIterator<Item = LexicographicObjective>,
+        params: &proto::SatParameters,
+    ) -> Vec<proto::CpSolverResponse> {
+        use proto::CpSolverStatus::{Feasible, Optimal};
+        let mut model = Self::from(self.proto.clone());
+        model.del_hints();
+        let mut responses: Vec<proto::CpSolverResponse> = Vec::new();
+        for objective in objectives {
+            if objective.maximize {
+                model.maximize(objective.expr.clone());
+            } else {
+                model.minimize(objective.expr.clone());
+            }
+            let response = model.solve_with_parameters(params);
+            if !matches!(response.status(), Optimal | Feasible) {
+                responses.push(response);
+                break;
+            }
+            let value = objective.expr.solution_value(&response);
+            if objective.maximize {
+                model.add_ge(objective.expr, value - objective.tolerance);
+            } else {
+                model.add_le(objective.expr, value + objective.tolerance);
+            }
+            model.hint_from_response(&response);
+            responses.push(response);
+        }
+        responses
+    }
+
+    /// Enumerates the Pareto front of two objectives to minimize, from
+    /// the best value of `obj_a` to the best value of `obj_b`.
+    ///
+    /// The front is computed by epsilon-constraint iterations: each
+    /// point minimizes `obj_a`, then `obj_b` at this value of
+    /// `obj_a`, and the next point must have a strictly better value
+    /// of `obj_b`. To maximize an objective, minimize its negation.
+    /// The objective and hints of the model are ignored, and the
+    /// model itself is not modified.
+    ///
+    /// Each solve uses the given [parameters][proto::SatParameters].
+    /// The enumeration stops when no other point exists, or at the
+    /// first [limit][ParetoLimits] reached. A point is only
+    /// guaranteed to be non-dominated if it is
+    /// [optimal][ParetoPoint::optimal].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, ParetoLimits};
+    /// # use cp_sat::proto::SatParameters;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 3)]);
+    /// let y = model.new_int_var([(0, 3)]);
+    /// model.add_ge([(1, x), (1, y)], 3);
+    /// let front = model.pareto_front(x, y, &SatParameters::default(), &ParetoLimits::default());
+    /// let values: Vec<_> = front.iter().map(|p| p.values).collect();
+    /// assert_eq!(values, [[0, 3], [1, 2], [2, 1], [3, 0]]);
+    /// assert!(front.iter().all(|p| p.optimal));
+    /// ```
+    pub fn pareto_front(
+        &self,
+        obj_a: impl Into<LinearExpr>,
+        obj_b: impl Into<LinearExpr>,
+        params: &proto::SatParameters,
+        limits: &ParetoLimits,
+    ) -> Vec<ParetoPoint> {
+        use proto::CpSolverStatus::{Feasible, Optimal};
+        let start = std::time::Instant::now();
+        let (obj_a, obj_b) = (obj_a.into(), obj_b.into());
+        let mut model = Self::from(self.proto.clone());
+        model.del_hints();
+        // The parameters of the next solve, limited to the remaining
+        // time, or None if the time limit is reached.
+        let remaining_params = || {
+            let mut params = params.clone();
+            if let Some(max_time) = limits.max_time_in_seconds {
+                let remaining = max_time - start.elapsed().as_secs_f64();
+                if remaining <= 0. {
+                    return None;
+                }
+                let time = params
+                    .max_time_in_seconds
+                    .map_or(remaining, |t| t.min(remaining));
+                params.max_time_in_seconds = Some(time);
+            }
+            Some(params)
+        };
+        let mut front = Vec::new();
+        loop {
+            if limits.max_points.is_some_and(|max| front.len() >= max) {
+                break;
+            }
+            let params = match remaining_params() {
+                Some(params) => params,
+                None => break,
+            };
+
+            model.minimize(obj_a.clone());
+            let first = model.solve_with_parameters(&params);
+            if !matches!(first.status(), Optimal | Feasible) {
+                break;
+            }
+            let a = obj_a.solution_value(&first);
+
+            let mut second_stage = Self::from(model.proto.clone());
+            second_stage.add_le(obj_a.clone(), a);
+            second_stage.minimize(obj_b.clone());
+            second_stage.hint_from_response(&first);
+            let second =
+                remaining_params().map(|params| second_stage.solve_with_parameters(&params));
+            let (optimal, response) = match second {
+                Some(second) if matches!(second.status(), Optimal | Feasible) => (
+                    first.status() == Optimal && second.status() == Optimal,
+                    second,
+                ),
+                _ => (false, first),
+            };
+            let b = obj_b.solution_value(&response);
+
+            model.add_le(obj_b.clone(), b - 1);
+            model.hint_from_response(&response);
+            front.push(ParetoPoint {
+                values: [a, b],
+                optimal,
+                response,
+            });
+        }
+        front
+    }
+
+    /// Writes the model to a file in the given [Format].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, Format};
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var_with_name([(0, 10)], "x");
+    /// model.maximize(x);
+    /// let path = std::env::temp_dir().join("cp_sat_write_to.pbtxt");
+    /// model.write_to(&path, Format::Text).unwrap();
+    /// let loaded = CpModelBuilder::read_from(&path).unwrap();
+    /// assert_eq!(model.proto(), loaded.proto());
+    /// assert_eq!(Some(x), loaded.var_by_name("x"));
+    /// ```
+    pub fn write_to(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        format: Format,
+    ) -> std::io::Result<()> {
+        let bytes = match format {
+            Format::Binary => self.proto.encode_to_vec(),
+            Format::Text => ffi::to_text_format(self.proto()).into_bytes(),
+            Format::Json => ffi::to_json(self.proto()).into_bytes(),
+        };
+        std::fs::write(path, bytes)
+    }
+
+    /// Reads a model written in any [Format].
+    ///
+    /// The format is deduced from the extension of the file (see
+    /// [Format::from_path]), or guessed from its content if the
+    /// extension is unknown. The variable handles can be recovered
+    /// with [CpModelBuilder::var_by_name] and
+    /// [CpModelBuilder::bool_var_by_name].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, Format};
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_bool_var_with_name("x");
+    /// model.add_or([x]);
+    /// let path = std::env::temp_dir().join("cp_sat_read_from.model");
+    /// model.write_to(&path, Format::Binary).unwrap();
+    /// let loaded = CpModelBuilder::read_from(&path).unwrap();
+    /// assert_eq!(model.proto(), loaded.proto());
+    /// assert_eq!(Some(x), loaded.bool_var_by_name("x"));
+    /// ```
+    pub fn read_from(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        Self::read_proto(path, Format::from_path(path))
+    }
+
+    /// Reads a model written in the given [Format], whatever the
+    /// extension of the file.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, Format};
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_bool_var_with_name("x");
+    /// model.add_or([x]);
+    /// let path = std::env::temp_dir().join("cp_sat_read_from_with_format.model");
+    /// model.write_to(&path, Format::Json).unwrap();
+    /// let loaded = CpModelBuilder::read_from_with_format(&path, Format::Json).unwrap();
+    /// assert_eq!(model.proto(), loaded.proto());
+    /// assert!(CpModelBuilder::read_from_with_format(&path, Format::Binary).is_err());
+    /// ```
+    pub fn read_from_with_format(
+        path: impl AsRef<std::path::Path>,
+        format: Format,
+    ) -> std::io::Result<Self> {
+        Self::read_proto(path.as_ref(), Some(format))
+    }
+
+    fn read_proto(path: &std::path::Path, format: Option<Format>) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let text = std::str::from_utf8(&bytes).ok();
+        let proto = match (format, text) {
+            (Some(Format::Binary), _) => proto::CpModelProto::decode(bytes.as_slice()).ok(),
+            (Some(Format::Text), Some(text)) => ffi::from_text_format(text),
+            (Some(Format::Json), Some(text)) => ffi::from_json(text),
+            (Some(_), None) => None,
+            (None, Some(text)) if text.trim_start().starts_with('{') => ffi::from_json(text),
+            (None, Some(text)) => ffi::from_text_format(text)
+                .or_else(|| proto::CpModelProto::decode(bytes.as_slice()).ok()),
+            (None, None) => proto::CpModelProto::decode(bytes.as_slice()).ok(),
+        };
+        proto.map(Self::from).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} is not a valid CpModelProto", path.display()),
+            )
+        })
+    }
+}
+
+impl From<proto::CpModelProto> for CpModelBuilder {
+    fn from(proto: proto::CpModelProto) -> Self {
+        Self {
+            proto,
+            ..Default::default()
+        }
+    }
+}
+
+/// How a [decision strategy][CpModelBuilder::add_decision_strategy]
+/// chooses the next variable to branch on, among the variables that
+/// are not fixed yet. Ties are broken by the order of the variables.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VariableSelection {
+    /// The first variable.
+    ChooseFirst,
+    /// The variable with the lowest lower bound.
+    ChooseLowestMin,
+    /// The variable with the highest upper bound.
+    ChooseHighestMax,
+    /// The variable with the smallest domain.
+    ChooseMinDomainSize,
+    /// The variable with the largest domain.
+    ChooseMaxDomainSize,
+}
+
+impl From<VariableSelection> for proto::decision_strategy_proto::VariableSelectionStrategy {
+    fn from(selection: VariableSelection) -> Self {
+        match selection {
+            VariableSelection::ChooseFirst => Self::ChooseFirst,
+            VariableSelection::ChooseLowestMin => Self::ChooseLowestMin,
+            VariableSelection::ChooseHighestMax => Self::ChooseHighestMax,
+            VariableSelection::ChooseMinDomainSize => Self::ChooseMinDomainSize,
+            VariableSelection::ChooseMaxDomainSize => Self::ChooseMaxDomainSize,
+        }
+    }
+}
+
+/// How a [decision strategy][CpModelBuilder::add_decision_strategy]
+/// restricts the domain of the chosen variable.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DomainReduction {
+    /// Fixes the variable to its lower bound.
+    SelectMinValue,
+    /// Fixes the variable to its upper bound.
+    SelectMaxValue,
+    /// Restricts the variable to the lower half of its domain.
+    SelectLowerHalf,
+    /// Restricts the variable to the upper half of its domain.
+    SelectUpperHalf,
+    /// Fixes the variable to the median value of its domain.
+    SelectMedianValue,
+}
+
+impl From<DomainReduction> for proto::decision_strategy_proto::DomainReductionStrategy {
+    fn from(reduction: DomainReduction) -> Self {
+        match reduction {
+            DomainReduction::SelectMinValue => Self::SelectMinValue,
+            DomainReduction::SelectMaxValue => Self::SelectMaxValue,
+            DomainReduction::SelectLowerHalf => Self::SelectLowerHalf,
+            DomainReduction::SelectUpperHalf => Self::SelectUpperHalf,
+            DomainReduction::SelectMedianValue => Self::SelectMedianValue,
+        }
+    }
+}
+
+/// Returns a copy of the parameters where the search strictly
+/// follows the
+/// [decision strategies][CpModelBuilder::add_decision_strategy] of
+/// the model: the `FIXED_SEARCH` branching with a single worker.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::builder::fixed_search_parameters;
+/// # use cp_sat::proto::{sat_parameters::SearchBranching, SatParameters};
+/// let mut params = SatParameters::default();
+/// params.max_time_in_seconds = Some(10.);
+/// let params = fixed_search_parameters(&params);
+/// assert_eq!(params.search_branching(), SearchBranching::FixedSearch);
+/// assert_eq!(params.num_search_workers, Some(1));
+/// assert_eq!(params.max_time_in_seconds, Some(10.));
+/// ```
+pub fn fixed_search_parameters(params: &proto::SatParameters) -> proto::SatParameters {
+    let mut params = params.clone();
+    params.set_search_branching(proto::sat_parameters::SearchBranching::FixedSearch);
+    params.num_search_workers = Some(1);
+    params
+}
+
+/// Returns a copy of the parameters where the solver tries to
+/// repair an infeasible [solution hint][CpModelBuilder::add_hint],
+/// for at most `conflict_limit` conflicts, before the regular search.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::builder::hint_repair_parameters;
+/// # use cp_sat::proto::SatParameters;
+/// let params = hint_repair_parameters(&SatParameters::default(), 100);
+/// assert_eq!(params.repair_hint, Some(true));
+/// assert_eq!(params.hint_conflict_limit, Some(100));
+/// ```
+pub fn hint_repair_parameters(
+    params: &proto::SatParameters,
+    conflict_limit: i32,
+) -> proto::SatParameters {
+    let mut params = params.clone();
+    params.repair_hint = Some(true);
+    params.hint_conflict_limit = Some(conflict_limit);
+    params
+}
+
+/// The file formats of a [proto::CpModelProto], used by
+/// [CpModelBuilder::write_to] and [CpModelBuilder::read_from].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// The protobuf binary wire format.
+    Binary,
+    /// The protobuf text format.
+    Text,
+    /// The protobuf JSON format.
+    Json,
+}
+
+impl Format {
+    /// Returns the format corresponding to the extension of the path:
+    /// `pb`, `bin` and `binpb` for [Format::Binary], `pbtxt`,
+    /// `textproto`, `prototxt` and `txt` for [Format::Text], and
+    /// `json` for [Format::Json].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::Format;
+    /// assert_eq!(Some(Format::Text), Format::from_path("model.pbtxt"));
+    /// assert_eq!(Some(Format::Json), Format::from_path("model.json"));
+    /// assert_eq!(None, Format::from_path("model"));
+    /// ```
+    pub fn from_path(path: impl AsRef<std::path::Path>) -> Option<Self> {
+        match path.as_ref().extension()?.to_str()? {
+            "pb" | "bin" | "binpb" => Some(Self::Binary),
+            "pbtxt" | "textproto" | "prototxt" | "txt" => Some(Self::Text),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Boolean variable identifier.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoolVar(pub(crate) i32);
+impl BoolVar {
+    /// Gets the solution value of the variable from a solution.
+    ///
+    /// The solution must come from the same model as the variable,
+    /// and a solution must be present in the response.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::{CpSolverStatus, SatParameters};
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_bool_var();
+    /// model.add_or([x]);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(x.solution_value(&response));
+    /// ```
+    #[track_caller]
+    pub fn solution_value(self, response: &proto::CpSolverResponse) -> bool {
+        if self.0 < 0 {
+            use std::ops::Not;
+            !self.not().solution_value(response)
+        } else {
+            response.solution[self.0 as usize] != 0
+        }
+    }
+}
+impl std::ops::Not for BoolVar {
+    type Output = Self;
+    fn not(self) -> Self {
+        Self(-self.0 - 1)
+    }
+}
+impl std::fmt::Debug for BoolVar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        if self.0 < 0 {
+            write!(f, "Not({:?})", !*self)
+        } else {
+            write!(f, "BoolVar({})", self.0)
+        }
+    }
+}
+
+/// Integer variable identifier.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntVar(pub(crate) i32);
+impl From<BoolVar> for IntVar {
+    fn from(bool_var: BoolVar) -> IntVar {
+        IntVar(bool_var.0)
+    }
+}
+impl IntVar {
+    /// Gets the solution value of the variable from a solution.
+    ///
+    /// The solution must come from the same model as the variable,
+    /// and a solution must be present in the response.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::proto::{CpSolverStatus, SatParameters};
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 42)]);
+    /// model.maximize(x);
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(42, x.solution_value(&response));
+    /// ```
+    #[track_caller]
+    pub fn solution_value(self, response: &proto::CpSolverResponse) -> i64 {
+        if self.0 < 0 {
+            1 - self.not().solution_value(response)
+        } else {
+            response.solution[self.0 as usize]
+        }
+    }
+    fn not(self) -> Self {
+        IntVar::from(!BoolVar(self.0))
+    }
+}
+
+/// An affine view `coeff * var + offset` of an integer variable.
+///
+/// It can be used as a [LinearExpr], and as the bounds of an
+/// [interval][CpModelBuilder::add_interval], without adding a
+/// variable to the model. An interval still comes with the linear
+/// constraints `start + size == end` and `size >= 0`.
+///
+/// The demands of [CpModelBuilder::add_cumulative] are variables in
+/// the CP SAT protobuf, not expressions: an affine demand needs an
+/// auxiliary variable, constrained to be equal to the view with
+/// [CpModelBuilder::add_eq].
+///
+/// The arithmetic operators panic if the coefficient or the offset
+/// overflows.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::builder::{AffineVar, CpModelBuilder};
+/// let mut model = CpModelBuilder::default();
+/// let x = model.new_int_var([(0, 10)]);
+/// let y = AffineVar::from(x) * 3 + 2; // y = 3x + 2
+/// assert_eq!(y, AffineVar::new(x, 3, 2));
+/// let z = model.new_int_var([(0, 100)]);
+/// model.add_max_eq(z, [y, AffineVar::new(x, -1, 20)]);
+/// model.add_le(y, 20);
+/// assert_eq!(model.proto().variables.len(), 2);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AffineVar {
+    var: IntVar,
+    coeff: i64,
+    offset: i64,
+}
+
+impl AffineVar {
+    /// Returns the view `coeff * var + offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `var` is a negated [BoolVar] and the normalized
+    /// coefficient or offset overflows.
+    #[track_caller]
+    pub fn new(var: impl Into<IntVar>, coeff: i64, offset: i64) -> Self {
+        let var = var.into();
+        if var.0 < 0 {
+            // coeff * (1 - v) + offset
+            Self {
+                var: var.not(),
+                coeff: checked(coeff.checked_neg()),
+                offset: checked(offset.checked_add(coeff)),
+            }
+        } else {
+            Self { var, coeff, offset }
+        }
+    }
+
+    /// Returns the variable of the view. It is never a negated
+    /// [BoolVar], as the negation is part of the coefficient and
+    /// offset.
+    pub fn var(self) -> IntVar {
+        self.var
+    }
+
+    /// Returns the coefficient of the variable.
+    pub fn coeff(self) -> i64 {
+        self.coeff
+    }
+
+    /// Returns the constant offset.
+    pub fn offset(self) -> i64 {
+        self.offset
+    }
+
+    /// Gets the solution value of the view from a solution.
+    ///
+    /// The solution must come from the same model as the variable,
+    /// else this method will panic or return a meaningless value.
+    #[track_caller]
+    pub fn solution_value(self, response: &proto::CpSolverResponse) -> i64 {
+        self.coeff * self.var.solution_value(response) + self.offset
+    }
+}
+
+impl<V: Into<IntVar>> From<V> for AffineVar {
+    fn from(var: V) -> Self {
+        Self::new(var, 1, 0)
+    }
+}
+
+/// Unwraps the result of a checked operation on an [AffineVar].
+#[track_caller]
+fn checked(value: Option<i64>) -> i64 {
+    value.expect("overflow in the coefficient or offset of an AffineVar")
+}
+
+impl std::ops::Mul<i64> for AffineVar {
+    type Output = AffineVar;
+    #[track_caller]
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self {
+            var: self.var,
+            coeff: checked(self.coeff.checked_mul(rhs)),
+            offset: checked(self.offset.checked_mul(rhs)),
+        }
+    }
+}
+
+impl std::ops::Add<i64> for AffineVar {
+    type Output = AffineVar;
+    #[track_caller]
+    fn add(mut self, rhs: i64) -> Self::Output {
+        self.offset = checked(self.offset.checked_add(rhs));
+        self
+    }
+}
+
+impl std::ops::Sub<i64> for AffineVar {
+    type Output = AffineVar;
+    #[track_caller]
+    fn sub(mut self, rhs: i64) -> Self::Output {
+        self.offset = checked(self.offset.checked_sub(rhs));
+        self
+    }
+}
+
+impl std::ops::Neg for AffineVar {
+    type Output = AffineVar;
+    #[track_caller]
+    fn neg(self) -> Self::Output {
+        self * -1
+    }
+}
+
+impl From<AffineVar> for LinearExpr {
+    fn from(var: AffineVar) -> Self {
+        LinearExpr::from((var.coeff, var.var)) + var.offset
+    }
+}
+
+impl From<AffineVar> for proto::LinearExpressionProto {
+    fn from(var: AffineVar) -> Self {
+        LinearExpr::from(var).into()
+    }
+}
+
+/// A boolean expression over [BoolVar]s, to be encoded with
+/// [CpModelBuilder::encode_bool_expr] or added as a constraint with
+/// [CpModelBuilder::add_bool_expr].
+///
+/// The `!`, `&` and `|` operators can be used to build expressions.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::builder::{BoolExpr, CpModelBuilder};
+/// let mut model = CpModelBuilder::default();
+/// let x = model.new_bool_var();
+/// let y = model.new_bool_var();
+/// let expr = BoolExpr::from(x) & !BoolExpr::from(y);
+/// assert_eq!(expr, BoolExpr::And(vec![BoolExpr::Var(x), BoolExpr::Var(!y)]));
+/// let z = model.encode_bool_expr(&(expr | BoolExpr::implies(y, x)));
+/// model.add_and([z]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoolExpr {
+    /// A literal.
+    Var(BoolVar),
+    /// The negation of an expression.
+    Not(Box<BoolExpr>),
+    /// The conjunction of expressions, true if empty.
+    And(Vec<BoolExpr>),
+    /// The disjunction of expressions, false if empty.
+    Or(Vec<BoolExpr>),
+    /// The implication between two expressions.
+    Implies(Box<BoolExpr>, Box<BoolExpr>),
+}
+
+impl BoolExpr {
+    /// Returns the conjunction of the expressions.
+    pub fn and(exprs: impl IntoIterator<Item = impl Into<BoolExpr>>) -> Self {
+        Self::And(exprs.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns the disjunction of the expressions.
+    pub fn or(exprs: impl IntoIterator<Item = impl Into<BoolExpr>>) -> Self {
+        Self::Or(exprs.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns the implication `a => b`.
+    pub fn implies(a: impl Into<BoolExpr>, b: impl Into<BoolExpr>) -> Self {
+        Self::Implies(Box::new(a.into()), Box::new(b.into()))
+    }
+}
+
+impl From<BoolVar> for BoolExpr {
+    fn from(var: BoolVar) -> Self {
+        Self::Var(var)
+    }
+}
+
+impl std::ops::Not for BoolExpr {
+    type Output = BoolExpr;
+    fn not(self) -> Self::Output {
+        match self {
+            BoolExpr::Var(var) => BoolExpr::Var(!var),
+            BoolExpr::Not(expr) => *expr,
+            expr => BoolExpr::Not(Box::new(expr)),
+        }
+    }
+}
+
+impl<T: Into<BoolExpr>> std::ops::BitAnd<T> for BoolExpr {
+    type Output = BoolExpr;
+    fn bitand(self, rhs: T) -> Self::Output {
+        match self {
+            BoolExpr::And(mut exprs) => {
+                exprs.push(rhs.into());
+                BoolExpr::And(exprs)
+            }
+            expr => BoolExpr::And(vec![expr, rhs.into()]),
+        }
+    }
+}
+
+impl<T: Into<BoolExpr>> std::ops::BitOr<T> for BoolExpr {
+    type Output = BoolExpr;
+    fn bitor(self, rhs: T) -> Self::Output {
+        match self {
+            BoolExpr::Or(mut exprs) => {
+                exprs.push(rhs.into());
+                BoolExpr::Or(exprs)
+            }
+            expr => BoolExpr::Or(vec![expr, rhs.into()]),
+        }
+    }
+}
+
+/// An objective of [CpModelBuilder::solve_lexicographic].
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::builder::{CpModelBuilder, LexicographicObjective};
+/// let mut model = CpModelBuilder::default();
+/// let cost = model.new_int_var([(0, 100)]);
+/// let delay = model.new_int_var([(0, 100)]);
+/// let objectives = [
+///     // accept a cost up to 5 above the optimum
+///     LexicographicObjective::minimize(cost).with_tolerance(5),
+///     LexicographicObjective::minimize(delay),
+/// ];
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LexicographicObjective {
+    expr: LinearExpr,
+    maximize: bool,
+    tolerance: i64,
+}
+
+impl LexicographicObjective {
+    /// An objective minimizing the given expression.
+    pub fn minimize(expr: impl Into<LinearExpr>) -> Self {
+        Self {
+            expr: expr.into(),
+            maximize: false,
+            tolerance: 0,
+        }
+    }
+    /// An objective maximizing the given expression.
+    pub fn maximize(expr: impl Into<LinearExpr>) -> Self {
+        Self {
+            expr: expr.into(),
+            maximize: true,
+            tolerance: 0,
+        }
+    }
+    /// Sets how much the objective may be degraded from its optimum
+    /// during the next stages. Defaults to 0, meaning that the
+    /// optimum is kept.
+    pub fn with_tolerance(mut self, tolerance: i64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+/// Limits of [CpModelBuilder::pareto_front].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParetoLimits {
+    /// Maximum number of points to enumerate. `None`, the default,
+    /// means no limit.
+    pub max_points: Option<usize>,
+    /// Maximum time of the whole enumeration, in seconds. `None`, the
+    /// default, means no limit.
+    pub max_time_in_seconds: Option<f64>,
+}
+
+/// A point of the Pareto front returned by
+/// [CpModelBuilder::pareto_front].
+#[derive(Clone, Debug)]
+pub struct ParetoPoint {
+    /// The values of the two objectives.
+    pub values: [i64; 2],
+    /// Whether both objectives were proven optimal for this point,
+    /// meaning that the point is non-dominated.
+    pub optimal: bool,
+    /// The response containing the solution of the point.
+    pub response: proto::CpSolverResponse,
+}
+
+/// A multi-dimensional array of variables, created by
+/// [CpModelBuilder::new_bool_var_array] and
+/// [CpModelBuilder::new_int_var_array].
+///
+/// The variables are stored in row-major order: the last index
+/// varies the fastest. An array can be indexed with an array of
+/// indices, and its rows, columns and slices are iterators of
+/// variables, that can be given directly to the builder methods.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
+/// let mut model = CpModelBuilder::default();
+/// // x[n][d][s]: nurse n works on day d for shift s
+/// let x = model.new_bool_var_array(&[4, 7, 3], "x");
+/// for d in 0..7 {
+///     for s in 0..3 {
+///         model.add_exactly_one(x.slice(&[None, Some(d), Some(s)]));
+///     }
+/// }
+/// for n in 0..4 {
+///     model.add_le(x.row(n).collect::<LinearExpr>(), 6);
+///     for day in x.slice(&[Some(n), None, None]).collect::<Vec<_>>().chunks(3) {
+///         model.add_at_most_one(day.iter().copied());
+///     }
+/// }
+/// assert_eq!(x.shape(), [4, 7, 3]);
+/// assert_eq!(x.get(&[3, 6, 2]), Some(x[[3, 6, 2]]));
+/// assert_eq!(x.get(&[4, 0, 0]), None);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VarArray<V> {
+    shape: Vec<usize>,
+    vars: Vec<V>,
+}
+
+impl<V: Copy> VarArray<V> {
+    fn from_fn(shape: &[usize], mut f: impl FnMut(&[usize]) -> V) -> Self {
+        let len = shape.iter().product();
+        let mut index = vec![0; shape.len()];
+        let mut vars = Vec::with_capacity(len);
+        for _ in 0..len {
+            vars.push(f(&index));
+            for (i, &size) in index.iter_mut().zip(shape).rev() {
+                *i += 1;
+                if *i < size {
+                    break;
+                }
+                *i = 0;
+            }
+        }
+        Self {
+            shape: shape.to_vec(),
+            vars,
+        }
+    }
+
+    /// Returns the size of each dimension of the array.
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// Returns the number of variables in the array.
+    pub fn len(&self) -> usize {
+        self.vars.len()
+    }
+
+    /// Returns true if the array contains no variable.
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+
+    /// Returns all the variables, in row-major order.
+    pub fn as_slice(&self) -> &[V] {
+        &self.vars
+    }
+
+    /// Iterates over all the variables, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = V> + '_ {
+        self.vars.iter().copied()
+    }
+
+    /// Returns the variable at the given index, or `None` if the
+    /// index is out of the shape.
+    pub fn get(&self, index: &[usize]) -> Option<V> {
+        self.offset(index).map(|offset| self.vars[offset])
+    }
+
+    /// Iterates over the variables whose index matches the pattern,
+    /// in row-major order. `None` in the pattern matches any index
+    /// in its dimension, and `Some(i)` only the index `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern has not one element per dimension.
+    #[track_caller]
+    pub fn slice<'a>(&'a self, pattern: &'a [Option<usize>]) -> impl Iterator<Item = V> + 'a {
+        assert_eq!(
+            pattern.len(),
+            self.shape.len(),
+            "the pattern must have one element per dimension"
+        );
+        let strides = self.strides();
+        self.vars
+            .iter()
+            .enumerate()
+            .filter(move |(offset, _)| {
+                pattern
+                    .iter()
+                    .zip(&self.shape)
+                    .zip(&strides)
+                    .all(|((p, &size), &stride)| p.map_or(true, |p| offset / stride % size == p))
+            })
+            .map(|(_, &var)| var)
+    }
+
+    /// Iterates over the variables with the first index `i`, e.g. the
+    /// row `i` of a 2-dimensional array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the array has no dimension, or if `i` is out of the
+    /// shape.
+    #[track_caller]
+    pub fn row(&self, i: usize) -> impl Iterator<Item = V> + '_ {
+        assert!(
+            !self.shape.is_empty(),
+            "the array must have at least one dimension"
+        );
+        assert!(i < self.shape[0], "row {} is out of the shape", i);
+        let len = self.vars.len() / self.shape[0];
+        self.vars[i * len..(i + 1) * len].iter().copied()
     }
-}
 
-/// Boolean variable identifier.
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct BoolVar(i32);
-impl BoolVar {
-    /// Gets the solution value of the variable from a solution.
+    /// Iterates over the variables with the second index `j`, e.g.
+    /// the column `j` of a 2-dimensional array.
     ///
-    /// The solution must come from the same model as the variable,
-    /// and a solution must be present in the response.
+    /// # Panics
+    ///
+    /// Panics if the array has less than two dimensions, or if `j` is
+    /// out of the shape.
+    #[track_caller]
+    pub fn column(&self, j: usize) -> impl Iterator<Item = V> + '_ {
+        assert!(
+            self.shape.len() >= 2,
+            "the array must have at least two dimensions"
+        );
+        assert!(j < self.shape[1], "column {} is out of the shape", j);
+        let strides = self.strides();
+        let (size, stride) = (self.shape[1], strides[1]);
+        self.vars
+            .iter()
+            .enumerate()
+            .filter(move |(offset, _)| offset / stride % size == j)
+            .map(|(_, &var)| var)
+    }
+
+    /// Returns the 1-dimensional lines of the array along the given
+    /// dimension, i.e. the variables with the same indices except in
+    /// this dimension. For a 2-dimensional array, the lines along the
+    /// dimension 1 are the rows, and along the dimension 0 the
+    /// columns.
     ///
     /// # Example
     ///
     /// ```
     /// # use cp_sat::builder::CpModelBuilder;
-    /// # use cp_sat::proto::{CpSolverStatus, SatParameters};
     /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_bool_var();
-    /// model.add_or([x]);
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert!(x.solution_value(&response));
+    /// let x = model.new_int_var_array(&[2, 3], [(0, 10)], "");
+    /// let columns = x.lines(0);
+    /// assert_eq!(columns.len(), 3);
+    /// assert_eq!(columns[2], [x[[0, 2]], x[[1, 2]]]);
+    /// assert_eq!(x.lines(1)[1], x.row(1).collect::<Vec<_>>());
     /// ```
     #[track_caller]
-    pub fn solution_value(self, response: &proto::CpSolverResponse) -> bool {
-        if self.0 < 0 {
-            use std::ops::Not;
-            !self.not().solution_value(response)
+    pub fn lines(&self, dimension: usize) -> Vec<Vec<V>> {
+        let (size, stride) = (self.shape[dimension], self.strides()[dimension]);
+        (0..self.vars.len())
+            .filter(|offset| offset / stride % size == 0)
+            .map(|start| (0..size).map(|k| self.vars[start + k * stride]).collect())
+            .collect()
+    }
+
+    fn offset(&self, index: &[usize]) -> Option<usize> {
+        if index.len() != self.shape.len() || index.iter().zip(&self.shape).any(|(i, s)| i >= s) {
+            return None;
+        }
+        Some(
+            index
+                .iter()
+                .zip(&self.shape)
+                .fold(0, |offset, (&i, &size)| offset * size + i),
+        )
+    }
+
+    fn strides(&self) -> Vec<usize> {
+        let mut strides = vec![1; self.shape.len()];
+        for d in (0..self.shape.len().saturating_sub(1)).rev() {
+            strides[d] = strides[d + 1] * self.shape[d + 1];
+        }
+        strides
+    }
+}
+
+impl<V: Copy, const N: usize> std::ops::Index<[usize; N]> for VarArray<V> {
+    type Output = V;
+    #[track_caller]
+    fn index(&self, index: [usize; N]) -> &V {
+        match self.offset(&index) {
+            Some(offset) => &self.vars[offset],
+            None => panic!("index {:?} is out of the shape {:?}", index, self.shape),
+        }
+    }
+}
+
+impl<'a, V: Copy> IntoIterator for &'a VarArray<V> {
+    type Item = V;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'a, V>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.vars.iter().copied()
+    }
+}
+
+/// The translation of the variables and constraints of a model
+/// merged into another one, returned by [CpModelBuilder::merge].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VarMap {
+    var_offset: i32,
+    constraint_offset: usize,
+}
+
+impl VarMap {
+    /// Returns the variable of the merged model corresponding to the
+    /// given variable.
+    pub fn var(&self, var: IntVar) -> IntVar {
+        IntVar(self.var_ref(var.0))
+    }
+    /// Returns the boolean variable of the merged model corresponding
+    /// to the given boolean variable.
+    pub fn bool_var(&self, var: BoolVar) -> BoolVar {
+        BoolVar(self.var_ref(var.0))
+    }
+    /// Returns the constraint of the merged model corresponding to the
+    /// given constraint.
+    pub fn constraint(&self, constraint: Constraint) -> Constraint {
+        Constraint(constraint.0 + self.constraint_offset)
+    }
+    fn var_ref(&self, var: i32) -> i32 {
+        if var < 0 {
+            var - self.var_offset
         } else {
-            response.solution[self.0 as usize] != 0
+            var + self.var_offset
         }
     }
 }
-impl std::ops::Not for BoolVar {
-    type Output = Self;
-    fn not(self) -> Self {
-        Self(-self.0 - 1)
+
+/// The domain of a variable: a sorted list of disjoint intervals,
+/// bounds included.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::builder::Domain;
+/// let domain = Domain::new([(0, 2), (4, 8)]);
+/// assert_eq!(domain.min(), Some(0));
+/// assert_eq!(domain.max(), Some(8));
+/// assert!(domain.contains(4));
+/// assert!(!domain.contains(3));
+/// assert_eq!(domain.intervals().collect::<Vec<_>>(), [(0, 2), (4, 8)]);
+/// assert_eq!(Domain::new([(5, 5)]).fixed_value(), Some(5));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Domain(Vec<i64>);
+
+impl Domain {
+    /// Creates a domain from a list of intervals. The intervals are
+    /// expected to be sorted and disjoint, as the domains of
+    /// [CpModelBuilder::new_int_var].
+    pub fn new(intervals: impl IntoIterator<Item = (i64, i64)>) -> Self {
+        Self(intervals.into_iter().flat_map(|(b, e)| [b, e]).collect())
+    }
+
+    /// Iterates over the intervals of the domain.
+    pub fn intervals(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.0.chunks_exact(2).map(|i| (i[0], i[1]))
+    }
+
+    /// Returns true if the domain contains no value.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the smallest value of the domain, if any.
+    pub fn min(&self) -> Option<i64> {
+        self.0.first().copied()
+    }
+
+    /// Returns the largest value of the domain, if any.
+    pub fn max(&self) -> Option<i64> {
+        self.0.last().copied()
+    }
+
+    /// Returns true if the value is in the domain.
+    pub fn contains(&self, value: i64) -> bool {
+        self.intervals().any(|(lb, ub)| lb <= value && value <= ub)
+    }
+
+    /// Returns the value of the domain if it has exactly one value.
+    pub fn fixed_value(&self) -> Option<i64> {
+        match self.0.as_slice() {
+            &[lb, ub] if lb == ub => Some(lb),
+            _ => None,
+        }
+    }
+
+    /// Returns the domain of `1 - x` for `x` in this domain, i.e. of
+    /// the negation of a literal.
+    fn negated(&self) -> Self {
+        Self(self.0.iter().rev().map(|&v| 1 - v).collect())
+    }
+
+    /// Returns the domain of the variable of a possibly negated
+    /// reference, given the domain of the reference.
+    fn literal(var: IntVar, domain: impl IntoIterator<Item = (i64, i64)>) -> Self {
+        let domain = Self::new(domain);
+        if var.0 < 0 {
+            domain.negated()
+        } else {
+            domain
+        }
     }
 }
-impl std::fmt::Debug for BoolVar {
+
+impl IntoIterator for Domain {
+    type Item = (i64, i64);
+    type IntoIter = std::vec::IntoIter<(i64, i64)>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals().collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl std::fmt::Display for Domain {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        if self.0 < 0 {
-            write!(f, "Not({:?})", !*self)
-        } else {
-            write!(f, "BoolVar({})", self.0)
+        for (i, (lb, ub)) in self.intervals().enumerate() {
+            if i > 0 {
+                write!(f, " ∪ ")?;
+            }
+            write!(f, "[{}, {}]", lb, ub)?;
         }
+        Ok(())
     }
 }
 
-/// Integer variable identifier.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct IntVar(i32);
-impl From<BoolVar> for IntVar {
-    fn from(bool_var: BoolVar) -> IntVar {
-        IntVar(bool_var.0)
+/// A name given to several variables or constraints, see
+/// [CpModelBuilder::set_strict_names].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DuplicateNameError {
+    /// The variable or constraint with a duplicate name.
+    pub location: Location,
+    /// The first variable or constraint with this name.
+    pub first: Location,
+}
+
+impl std::fmt::Display for DuplicateNameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let first = match &self.first {
+            Location::Variable { index, .. } => format!("variable #{}", index),
+            Location::Constraint { index, .. } => format!("constraint #{}", index),
+            location => location.to_string(),
+        };
+        write!(f, "{}: name already used by {}", self.location, first)
     }
 }
-impl IntVar {
-    /// Gets the solution value of the variable from a solution.
-    ///
-    /// The solution must come from the same model as the variable,
-    /// and a solution must be present in the response.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// # use cp_sat::builder::CpModelBuilder;
-    /// # use cp_sat::proto::{CpSolverStatus, SatParameters};
-    /// let mut model = CpModelBuilder::default();
-    /// let x = model.new_int_var([(0, 42)]);
-    /// model.maximize(x);
-    /// let response = model.solve();
-    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
-    /// assert_eq!(42, x.solution_value(&response));
-    /// ```
-    #[track_caller]
-    pub fn solution_value(self, response: &proto::CpSolverResponse) -> i64 {
-        if self.0 < 0 {
-            1 - self.not().solution_value(response)
-        } else {
-            response.solution[self.0 as usize]
+
+impl std::error::Error for DuplicateNameError {}
+
+/// The indices of the variables and constraints by name, in
+/// increasing order. Empty names are not indexed.
+#[derive(Clone, Debug, Default)]
+struct NameIndex {
+    vars: HashMap<String, Vec<usize>>,
+    constraints: HashMap<String, Vec<usize>>,
+}
+
+impl NameIndex {
+    fn new(model: &proto::CpModelProto) -> Self {
+        let mut res = Self::default();
+        for (index, var) in model.variables.iter().enumerate() {
+            Self::insert(&mut res.vars, &var.name, index);
+        }
+        for (index, cst) in model.constraints.iter().enumerate() {
+            Self::insert(&mut res.constraints, &cst.name, index);
         }
+        res
     }
-    fn not(self) -> Self {
-        IntVar::from(!BoolVar(self.0))
+    fn insert(map: &mut HashMap<String, Vec<usize>>, name: &str, index: usize) {
+        if name.is_empty() {
+            return;
+        }
+        let indices = map.entry(name.to_string()).or_default();
+        if let Err(pos) = indices.binary_search(&index) {
+            indices.insert(pos, index);
+        }
+    }
+    fn remove(map: &mut HashMap<String, Vec<usize>>, name: &str, index: usize) {
+        if let Some(indices) = map.get_mut(name) {
+            indices.retain(|&i| i != index);
+            if indices.is_empty() {
+                map.remove(name);
+            }
+        }
     }
 }
 
 /// Constraint identifier.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Constraint(usize);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Constraint(pub(crate) usize);
 
 /// A linear expression, used in several places in the
 /// [builder][CpModelBuilder].
@@ -905,12 +3909,45 @@ pub struct Constraint(usize);
 /// expr.extend(vars.iter().map(|&v| (2, v))); // means expr += sum_vars(2 * v)
 /// ```
 #[derive(Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinearExpr {
     vars: SmallVec<[i32; 4]>,
     coeffs: SmallVec<[i64; 2]>,
     constant: i64,
 }
 
+impl LinearExpr {
+    /// Gets the solution value of the expression from a solution.
+    ///
+    /// The solution must come from the same model as the variables
+    /// of the expression, else this method will panic or return a
+    /// meaningless value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::{CpModelBuilder, LinearExpr};
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_int_var([(0, 10)]);
+    /// let b = model.new_bool_var();
+    /// let expr = LinearExpr::from([(2, x), (3, b.into())]) + 1;
+    /// model.maximize(expr.clone());
+    /// let response = model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert_eq!(24, expr.solution_value(&response));
+    /// ```
+    #[track_caller]
+    pub fn solution_value(&self, response: &proto::CpSolverResponse) -> i64 {
+        self.vars
+            .iter()
+            .zip(&self.coeffs)
+            .map(|(&var, &coeff)| coeff * IntVar(var).solution_value(response))
+            .sum::<i64>()
+            + self.constant
+    }
+}
+
 impl<E: Into<LinearExpr>> std::ops::AddAssign<E> for LinearExpr {
     fn add_assign(&mut self, rhs: E) {
         let mut rhs = rhs.into();
@@ -1015,3 +4052,193 @@ impl<T: Into<LinearExpr>> std::iter::FromIterator<T> for LinearExpr {
         res
     }
 }
+
+/// A linear expression with floating point coefficients, used by
+/// [CpModelBuilder::minimize_float] and
+/// [CpModelBuilder::maximize_float].
+///
+/// It describes an expression in the form `ax+by+c` where `a`, `b`
+/// and `c` are floating point numbers. It can be built like a
+/// [LinearExpr], and from a [LinearExpr].
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::builder::{CpModelBuilder, FloatLinearExpr, LinearExpr};
+/// let mut model = CpModelBuilder::default();
+/// let x = model.new_int_var([(0, 100)]);
+/// let y = model.new_bool_var();
+/// let mut expr = FloatLinearExpr::from([(0.5, x), (1.25, y.into())]);
+/// expr += LinearExpr::from([(2, x)]);
+/// expr -= (0.1, y);
+/// expr += 9.99;
+/// model.minimize_float(expr, 4);
+/// ```
+#[derive(Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FloatLinearExpr {
+    vars: SmallVec<[i32; 4]>,
+    coeffs: SmallVec<[f64; 2]>,
+    constant: f64,
+}
+
+impl FloatLinearExpr {
+    /// Gets the solution value of the expression from a solution.
+    ///
+    /// The solution must come from the same model as the variables
+    /// of the expression, else this method will panic or return a
+    /// meaningless value.
+    #[track_caller]
+    pub fn solution_value(&self, response: &proto::CpSolverResponse) -> f64 {
+        self.vars
+            .iter()
+            .zip(&self.coeffs)
+            .map(|(&var, &coeff)| coeff * IntVar(var).solution_value(response) as f64)
+            .sum::<f64>()
+            + self.constant
+    }
+}
+
+impl<E: Into<FloatLinearExpr>> std::ops::AddAssign<E> for FloatLinearExpr {
+    fn add_assign(&mut self, rhs: E) {
+        let mut rhs = rhs.into();
+        if self.vars.len() < rhs.vars.len() {
+            std::mem::swap(self, &mut rhs);
+        }
+        self.vars.extend_from_slice(&rhs.vars);
+        self.coeffs.extend_from_slice(&rhs.coeffs);
+        self.constant += rhs.constant;
+    }
+}
+impl std::ops::Neg for FloatLinearExpr {
+    type Output = FloatLinearExpr;
+    fn neg(mut self) -> Self::Output {
+        for c in &mut self.coeffs {
+            *c = -*c;
+        }
+        self.constant = -self.constant;
+        self
+    }
+}
+impl<L: Into<FloatLinearExpr>> std::ops::SubAssign<L> for FloatLinearExpr {
+    fn sub_assign(&mut self, rhs: L) {
+        *self += -rhs.into();
+    }
+}
+
+impl<V: Into<IntVar>> From<V> for FloatLinearExpr {
+    fn from(var: V) -> Self {
+        Self::from((1., var))
+    }
+}
+impl From<f64> for FloatLinearExpr {
+    fn from(constant: f64) -> Self {
+        let mut res = Self::default();
+        res.constant += constant;
+        res
+    }
+}
+impl<V: Into<IntVar>> From<(f64, V)> for FloatLinearExpr {
+    fn from((coeff, var): (f64, V)) -> Self {
+        let mut res = Self::default();
+        let var = var.into();
+        if var.0 < 0 {
+            res.vars.push(var.not().0);
+            res.coeffs.push(-coeff);
+            res.constant += coeff;
+        } else {
+            res.vars.push(var.0);
+            res.coeffs.push(coeff);
+        }
+        res
+    }
+}
+impl<V: Into<IntVar>, const L: usize> From<[(f64, V); L]> for FloatLinearExpr {
+    fn from(expr: [(f64, V); L]) -> Self {
+        let mut res = Self::default();
+        for term in expr {
+            res += term;
+        }
+        res
+    }
+}
+impl From<LinearExpr> for FloatLinearExpr {
+    fn from(expr: LinearExpr) -> Self {
+        Self {
+            vars: expr.vars,
+            coeffs: expr.coeffs.iter().map(|&c| c as f64).collect(),
+            constant: expr.constant as f64,
+        }
+    }
+}
+
+impl<T: Into<FloatLinearExpr>> std::ops::Add<T> for FloatLinearExpr {
+    type Output = FloatLinearExpr;
+    fn add(mut self, rhs: T) -> Self::Output {
+        self += rhs.into();
+        self
+    }
+}
+
+impl<T: Into<FloatLinearExpr>> std::ops::Sub<T> for FloatLinearExpr {
+    type Output = FloatLinearExpr;
+    fn sub(mut self, rhs: T) -> Self::Output {
+        self -= rhs.into();
+        self
+    }
+}
+
+impl<T: Into<FloatLinearExpr>> std::iter::Extend<T> for FloatLinearExpr {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for e in iter {
+            *self += e;
+        }
+    }
+}
+impl<T: Into<FloatLinearExpr>> std::iter::FromIterator<T> for FloatLinearExpr {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut res = FloatLinearExpr::default();
+        res.extend(iter);
+        res
+    }
+}
+
+/// Returns the intersection of two domains, as flattened lists of
+/// sorted intervals.
+fn intersect_domains(a: &[i64], b: &[i64]) -> Vec<i64> {
+    let mut res = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i + 1 < a.len() && j + 1 < b.len() {
+        let (lb, ub) = (a[i].max(b[j]), a[i + 1].min(b[j + 1]));
+        if lb <= ub {
+            res.extend([lb, ub]);
+        }
+        if a[i + 1] < b[j + 1] {
+            i += 2;
+        } else {
+            j += 2;
+        }
+    }
+    res
+}
+
+/// Returns true if the literal is fixed to false.
+fn is_false_literal(variables: &[proto::IntegerVariableProto], literal: i32) -> bool {
+    let fixed = if literal < 0 { 1 } else { 0 };
+    variables
+        .get(refs::var_index(literal))
+        .is_some_and(|v| v.domain == [fixed, fixed])
+}
+
+/// Returns the name of the variable at the given index of an array,
+/// empty if the prefix is empty.
+fn array_var_name(prefix: &str, index: &[usize]) -> String {
+    if prefix.is_empty() {
+        return String::new();
+    }
+    let mut name = prefix.to_string();
+    for i in index {
+        name.push_str(&format!("[{}]", i));
+    }
+    name
+}
@@ -2,6 +2,8 @@
 
 #include <ortools/sat/cp_model.h>
 #include <ortools/sat/cp_model_checker.h>
+#include <google/protobuf/text_format.h>
+#include <google/protobuf/util/json_util.h>
 
 namespace sat = operations_research::sat;
 
@@ -104,3 +106,81 @@ cp_sat_wrapper_solution_is_feasible(
 
     return sat::SolutionIsFeasible(model, variable_values);
 }
+
+// Message types for the text format and JSON conversions, must be
+// kept in sync with `ffi::CpSatMessage` implementations. Returns
+// nullptr for an unknown type, which must be checked by the callers.
+static std::unique_ptr<google::protobuf::Message>
+new_message(int message_type) {
+    switch (message_type) {
+    case 0: return std::make_unique<sat::CpModelProto>();
+    case 1: return std::make_unique<sat::CpSolverResponse>();
+    case 2: return std::make_unique<sat::SatParameters>();
+    default: return nullptr;
+    }
+}
+
+static unsigned char*
+serialize_message(const google::protobuf::Message& message, size_t* out_size) {
+    *out_size = message.ByteSizeLong();
+    // Never allocates 0 bytes, as a null pointer means a parse error.
+    unsigned char* out_buf = (unsigned char*) malloc(std::max<size_t>(*out_size, 1));
+    const bool res = message.SerializeToArray(out_buf, *out_size);
+    assert(res);
+    return out_buf;
+}
+
+extern "C" char*
+cp_sat_wrapper_to_text_format(int message_type, unsigned char* buf, size_t size) {
+    std::unique_ptr<google::protobuf::Message> message = new_message(message_type);
+    if (message == nullptr) {
+        return strdup("");
+    }
+    const bool res = message->ParseFromArray(buf, size);
+    assert(res);
+
+    std::string text;
+    google::protobuf::TextFormat::PrintToString(*message, &text);
+    return strdup(text.c_str());
+}
+
+extern "C" unsigned char*
+cp_sat_wrapper_from_text_format(int message_type, const char* text, size_t* out_size) {
+    std::unique_ptr<google::protobuf::Message> message = new_message(message_type);
+    if (message == nullptr) {
+        return nullptr;
+    }
+    if (!google::protobuf::TextFormat::ParseFromString(text, message.get())) {
+        return nullptr;
+    }
+    return serialize_message(*message, out_size);
+}
+
+extern "C" char*
+cp_sat_wrapper_to_json(int message_type, unsigned char* buf, size_t size) {
+    std::unique_ptr<google::protobuf::Message> message = new_message(message_type);
+    if (message == nullptr) {
+        return strdup("");
+    }
+    const bool res = message->ParseFromArray(buf, size);
+    assert(res);
+
+    std::string json;
+    google::protobuf::util::JsonPrintOptions options;
+    options.add_whitespace = true;
+    const auto status = google::protobuf::util::MessageToJsonString(*message, &json, options);
+    assert(status.ok());
+    return strdup(json.c_str());
+}
+
+extern "C" unsigned char*
+cp_sat_wrapper_from_json(int message_type, const char* json, size_t* out_size) {
+    std::unique_ptr<google::protobuf::Message> message = new_message(message_type);
+    if (message == nullptr) {
+        return nullptr;
+    }
+    if (!google::protobuf::util::JsonStringToMessage(json, message.get()).ok()) {
+        return nullptr;
+    }
+    return serialize_message(*message, out_size);
+}
@@ -0,0 +1,188 @@
+use crate::refs;
+use crate::{proto, stats::ConstraintKind};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// Options of [to_dot].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DotOptions {
+    /// Linear constraints and objectives with more variables than
+    /// this threshold are collapsed: they are drawn without their
+    /// edges, and labelled with their number of variables. `None`
+    /// never collapses. Defaults to 50.
+    pub max_linear_size: Option<usize>,
+    /// Do not draw the variables used in no constraint nor in the
+    /// objective. Defaults to false.
+    pub hide_unused_variables: bool,
+}
+
+impl Default for DotOptions {
+    fn default() -> Self {
+        Self {
+            max_linear_size: Some(50),
+            hide_unused_variables: false,
+        }
+    }
+}
+
+/// Returns the fill color of the constraints of the given kind.
+fn color(kind: ConstraintKind) -> &'static str {
+    use ConstraintKind::*;
+    match kind {
+        Empty => "gray",
+        BoolOr | BoolAnd | AtMostOne | ExactlyOne | BoolXor => "lightblue",
+        Linear => "lightyellow",
+        IntDiv | IntMod | IntMax | LinMax | IntMin | LinMin | IntProd => "orange",
+        AllDiff | Element | Circuit | Routes | Table | Automaton | Inverse => "palegreen",
+        Reservoir | Interval | NoOverlap | NoOverlap2d | Cumulative => "plum",
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn domain_to_string(domain: &[i64]) -> String {
+    let intervals: Vec<String> = domain
+        .chunks(2)
+        .map(|i| match i {
+            [lb, ub] if lb == ub => lb.to_string(),
+            [lb, ub] => format!("[{}, {}]", lb, ub),
+            _ => "?".into(),
+        })
+        .collect();
+    intervals.join(" ∪ ")
+}
+
+/// Returns the bipartite variable-constraint graph of the model in the
+/// GraphViz DOT format.
+///
+/// Variables are ellipses labelled with their name and domain.
+/// Constraints are boxes labelled with their name and kind, and
+/// colored by kind. A constraint is linked to its variables, with
+/// dashed edges for its enforcement literals, and to the intervals it
+/// uses with bold edges. The objective is an octagon linked to its
+/// variables.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::builder::CpModelBuilder;
+/// # use cp_sat::dot::{to_dot, DotOptions};
+/// let mut model = CpModelBuilder::default();
+/// let x = model.new_int_var_with_name([(0, 10)], "x");
+/// let b = model.new_bool_var_with_name("b");
+/// let c = model.add_le([(1, x), (5, b.into())], 8);
+/// model.set_constraint_name(c, "capacity");
+/// let dot = to_dot(model.proto(), &DotOptions::default());
+/// assert!(dot.starts_with("graph model {\n"));
+/// assert!(dot.contains("  v0 [label=\"x\\n[0, 10]\"];\n"));
+/// assert!(dot.contains("  c0 [label=\"capacity\\nlinear\", shape=box, fillcolor=lightyellow];\n"));
+/// assert!(dot.contains("  c0 -- v1;\n"));
+/// ```
+pub fn to_dot(model: &proto::CpModelProto, options: &DotOptions) -> String {
+    let collapsed = |num_vars: usize| options.max_linear_size.is_some_and(|max| num_vars > max);
+    let mut used = vec![false; model.variables.len()];
+    let mut body = String::new();
+
+    for (index, cst) in model.constraints.iter().enumerate() {
+        let kind = ConstraintKind::of(cst);
+        let vars: BTreeSet<usize> = refs::vars(cst).into_iter().collect();
+        let enforcement: BTreeSet<usize> = cst
+            .enforcement_literal
+            .iter()
+            .map(|&l| refs::var_index(l))
+            .collect();
+        let name = if cst.name.is_empty() {
+            format!("#{}", index)
+        } else {
+            escape(&cst.name)
+        };
+        let is_collapsed = kind == ConstraintKind::Linear && collapsed(vars.len());
+        let label = if is_collapsed {
+            format!("{}\\n{} ({} variables)", name, kind, vars.len())
+        } else {
+            format!("{}\\n{}", name, kind)
+        };
+        writeln!(
+            body,
+            "  c{} [label=\"{}\", shape=box, fillcolor={}{}];",
+            index,
+            label,
+            color(kind),
+            if is_collapsed {
+                ", style=\"filled,dashed\""
+            } else {
+                ""
+            }
+        )
+        .unwrap();
+        for &var in &vars {
+            if let Some(used) = used.get_mut(var) {
+                *used = true;
+            }
+            if is_collapsed {
+                continue;
+            }
+            if enforcement.contains(&var) {
+                writeln!(body, "  c{} -- v{} [style=dashed];", index, var).unwrap();
+            } else {
+                writeln!(body, "  c{} -- v{};", index, var).unwrap();
+            }
+        }
+        for interval in refs::intervals(cst) {
+            writeln!(body, "  c{} -- c{} [style=bold];", index, interval).unwrap();
+        }
+    }
+
+    if let Some(objective) = &model.objective {
+        let vars: BTreeSet<usize> = objective.vars.iter().map(|&v| refs::var_index(v)).collect();
+        let is_collapsed = collapsed(vars.len());
+        writeln!(
+            body,
+            "  objective [label=\"objective{}\", shape=octagon, fillcolor=gold];",
+            if is_collapsed {
+                format!("\\n({} variables)", vars.len())
+            } else {
+                String::new()
+            }
+        )
+        .unwrap();
+        for var in vars {
+            if let Some(used) = used.get_mut(var) {
+                *used = true;
+            }
+            if !is_collapsed {
+                writeln!(body, "  objective -- v{};", var).unwrap();
+            }
+        }
+    }
+
+    let mut dot = String::from("graph model {\n  node [style=filled, fillcolor=white];\n");
+    for (index, var) in model.variables.iter().enumerate() {
+        if options.hide_unused_variables && !used[index] {
+            continue;
+        }
+        let name = if var.name.is_empty() {
+            format!("#{}", index)
+        } else {
+            escape(&var.name)
+        };
+        if var.domain == [0, 1] {
+            writeln!(dot, "  v{} [label=\"{}\"];", index, name).unwrap();
+        } else {
+            writeln!(
+                dot,
+                "  v{} [label=\"{}\\n{}\"];",
+                index,
+                name,
+                domain_to_string(&var.domain)
+            )
+            .unwrap();
+        }
+    }
+    dot.push_str(&body);
+    dot.push_str("}\n");
+    dot
+}
@@ -1,7 +1,7 @@
 use crate::proto;
 use libc::c_char;
 use prost::Message;
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 
 extern "C" {
     fn cp_sat_wrapper_solve(
@@ -29,6 +29,19 @@ extern "C" {
         solution_buf: *const i64,
         solution_size: usize,
     ) -> bool;
+    fn cp_sat_wrapper_to_text_format(message_type: i32, buf: *const u8, size: usize)
+        -> *mut c_char;
+    fn cp_sat_wrapper_from_text_format(
+        message_type: i32,
+        text: *const c_char,
+        out_size: &mut usize,
+    ) -> *mut u8;
+    fn cp_sat_wrapper_to_json(message_type: i32, buf: *const u8, size: usize) -> *mut c_char;
+    fn cp_sat_wrapper_from_json(
+        message_type: i32,
+        json: *const c_char,
+        out_size: &mut usize,
+    ) -> *mut u8;
 }
 
 /// Solves the given [CpModelProto][crate::proto::CpModelProto] and
@@ -156,3 +169,116 @@ pub fn solution_is_feasible(model: &proto::CpModelProto, solution: &[i64]) -> bo
         )
     }
 }
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for crate::proto::CpModelProto {}
+    impl Sealed for crate::proto::CpSolverResponse {}
+    impl Sealed for crate::proto::SatParameters {}
+}
+
+/// A CP SAT protobuf message that can be converted from and to the
+/// protobuf text and JSON formats.
+///
+/// This trait is sealed: it is implemented for the messages known by
+/// the C++ wrapper, and cannot be implemented outside of this crate.
+pub trait CpSatMessage: Message + Default + private::Sealed {
+    #[doc(hidden)]
+    const MESSAGE_TYPE: i32;
+}
+impl CpSatMessage for proto::CpModelProto {
+    const MESSAGE_TYPE: i32 = 0;
+}
+impl CpSatMessage for proto::CpSolverResponse {
+    const MESSAGE_TYPE: i32 = 1;
+}
+impl CpSatMessage for proto::SatParameters {
+    const MESSAGE_TYPE: i32 = 2;
+}
+
+/// Returns the given message in the protobuf text format.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::ffi::to_text_format;
+/// # use cp_sat::proto::SatParameters;
+/// let mut params = SatParameters::default();
+/// params.num_search_workers = Some(8);
+/// assert_eq!("num_search_workers: 8\n", to_text_format(&params));
+/// ```
+pub fn to_text_format<M: CpSatMessage>(message: &M) -> String {
+    let buf = message.encode_to_vec();
+    let char_ptr =
+        unsafe { cp_sat_wrapper_to_text_format(M::MESSAGE_TYPE, buf.as_ptr(), buf.len()) };
+    let res = unsafe { CStr::from_ptr(char_ptr) }
+        .to_str()
+        .unwrap()
+        .to_owned();
+    unsafe { libc::free(char_ptr as _) };
+    res
+}
+
+/// Parses a message in the protobuf text format. Returns `None` if
+/// the text is not a valid message.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::ffi::from_text_format;
+/// # use cp_sat::proto::SatParameters;
+/// let params: SatParameters = from_text_format("max_time_in_seconds: 10").unwrap();
+/// assert_eq!(Some(10.), params.max_time_in_seconds);
+/// assert!(from_text_format::<SatParameters>("not a field: 1").is_none());
+/// ```
+pub fn from_text_format<M: CpSatMessage>(text: &str) -> Option<M> {
+    let text = CString::new(text).ok()?;
+    let mut out_size = 0;
+    let res =
+        unsafe { cp_sat_wrapper_from_text_format(M::MESSAGE_TYPE, text.as_ptr(), &mut out_size) };
+    decode_owned_buffer(res, out_size)
+}
+
+/// Returns the given message in the protobuf JSON format.
+pub fn to_json<M: CpSatMessage>(message: &M) -> String {
+    let buf = message.encode_to_vec();
+    let char_ptr = unsafe { cp_sat_wrapper_to_json(M::MESSAGE_TYPE, buf.as_ptr(), buf.len()) };
+    let res = unsafe { CStr::from_ptr(char_ptr) }
+        .to_str()
+        .unwrap()
+        .to_owned();
+    unsafe { libc::free(char_ptr as _) };
+    res
+}
+
+/// Parses a message in the protobuf JSON format. Returns `None` if
+/// the JSON is not a valid message.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::ffi::{from_json, to_json};
+/// # use cp_sat::proto::CpModelProto;
+/// let mut model = CpModelProto::default();
+/// model.name = "model".into();
+/// let json = to_json(&model);
+/// assert_eq!(Some(model), from_json(&json));
+/// ```
+pub fn from_json<M: CpSatMessage>(json: &str) -> Option<M> {
+    let json = CString::new(json).ok()?;
+    let mut out_size = 0;
+    let res = unsafe { cp_sat_wrapper_from_json(M::MESSAGE_TYPE, json.as_ptr(), &mut out_size) };
+    decode_owned_buffer(res, out_size)
+}
+
+/// Decodes and frees a buffer allocated by the wrapper, `None` if the
+/// pointer is null.
+fn decode_owned_buffer<M: Message + Default>(buf: *mut u8, size: usize) -> Option<M> {
+    if buf.is_null() {
+        return None;
+    }
+    let slice = unsafe { std::slice::from_raw_parts(buf, size) };
+    let message = M::decode(slice).unwrap();
+    unsafe { libc::free(buf as _) };
+    Some(message)
+}
@@ -0,0 +1,316 @@
+//! Reader and writer for the DIMACS CNF and WCNF formats of SAT and
+//! MaxSAT instances.
+//!
+//! Hard clauses are added with
+//! [add_or][crate::builder::CpModelBuilder::add_or]. Each soft clause
+//! of a WCNF file gets a relaxation literal, true if the clause is
+//! violated, and the weighted sum of the relaxation literals is
+//! minimized. Both the classic WCNF format, with a `p wcnf` header and
+//! an optional top weight, and the 2022 format, with `h` for hard
+//! clauses, are supported.
+//!
+//! # Example
+//!
+//! ```
+//! # use cp_sat::formats::dimacs;
+//! let wcnf = "\
+//! c x1 or x2, prefer not x1 and not x2
+//! p wcnf 2 3 10
+//! 10 1 2 0
+//! 3 -1 0
+//! 2 -2 0
+//! ";
+//! let instance = dimacs::parse(wcnf).unwrap();
+//! assert_eq!(2, instance.vars.len());
+//! assert_eq!(2, instance.soft_clauses.len());
+//! assert_eq!(1, instance.model.proto().constraints.len());
+//! let objective = instance.model.proto().objective.as_ref().unwrap();
+//! assert_eq!(vec![3, 2], objective.coeffs);
+//! ```
+
+use super::Error;
+use crate::builder::{BoolVar, CpModelBuilder, LinearExpr};
+use crate::proto;
+use crate::refs::var_index;
+use proto::constraint_proto::Constraint as CstEnum;
+use std::convert::TryFrom;
+
+/// A soft clause of a MaxSAT instance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SoftClause {
+    /// Literal that is true if the clause is violated.
+    pub relaxation: BoolVar,
+    /// The cost of violating the clause.
+    pub weight: i64,
+}
+
+/// A SAT or MaxSAT instance translated to CP SAT.
+#[derive(Debug)]
+pub struct DimacsModel {
+    /// The translated model.
+    pub model: CpModelBuilder,
+    /// The variables of the instance, `vars[i]` being the DIMACS
+    /// variable `i + 1`.
+    pub vars: Vec<BoolVar>,
+    /// The soft clauses of the instance, empty for CNF instances.
+    pub soft_clauses: Vec<SoftClause>,
+}
+
+/// Parses an instance in the DIMACS CNF or WCNF format.
+///
+/// The number of variables of the problem line is only a hint, ignored
+/// if it is larger than the number of bytes of the text: the
+/// variables are then created up to the largest referenced index. For
+/// the same reason, variable indices larger than the number of bytes
+/// of the text are rejected.
+pub fn parse(text: &str) -> Result<DimacsModel, Error> {
+    // Bounds the allocations by the size of the input.
+    let max_vars = text.len();
+    let mut instance = DimacsModel {
+        model: CpModelBuilder::default(),
+        vars: Vec::new(),
+        soft_clauses: Vec::new(),
+    };
+    // Files in the 2022 WCNF format have no problem line.
+    let mut weighted = text.lines().any(|l| l.trim_start().starts_with('h'));
+    let mut top = None;
+    let mut objective = LinearExpr::default();
+    let mut clause = Vec::new();
+    // The weight of the current clause, `None` for a hard clause.
+    let mut weight = None;
+    let mut in_clause = false;
+
+    for (line_index, line) in text.lines().enumerate() {
+        let line_number = line_index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('c') {
+            continue;
+        }
+        if line.starts_with('%') {
+            break;
+        }
+        if line.starts_with('p') {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.get(1) {
+                Some(&"cnf") if fields.len() == 4 => {}
+                Some(&"wcnf") if fields.len() == 4 || fields.len() == 5 => {
+                    weighted = true;
+                    top = fields
+                        .get(4)
+                        .map(|t| parse_int(t, line_number))
+                        .transpose()?;
+                }
+                _ => return Err(Error::parse(line_number, "invalid problem line")),
+            }
+            let num_vars = parse_int(fields[2], line_number)?;
+            match usize::try_from(num_vars) {
+                Ok(num_vars) if num_vars <= max_vars => new_vars(&mut instance, num_vars),
+                Ok(_) => {}
+                Err(_) => {
+                    return Err(Error::parse(
+                        line_number,
+                        format!("invalid number of variables {}", num_vars),
+                    ))
+                }
+            }
+            continue;
+        }
+
+        let mut tokens = line.split_whitespace();
+        if !in_clause && line.starts_with('h') {
+            weight = None;
+            in_clause = true;
+            tokens.next();
+        }
+        for token in tokens {
+            if !in_clause {
+                in_clause = true;
+                if weighted {
+                    let w = parse_int(token, line_number)?;
+                    if w < 0 {
+                        return Err(Error::parse(line_number, "negative weight"));
+                    }
+                    weight = Some(w).filter(|&w| top.map_or(true, |top| w < top));
+                    continue;
+                }
+                weight = None;
+            }
+            let lit = parse_int(token, line_number)?;
+            if lit == 0 {
+                add_clause(&mut instance, &mut objective, &clause, weight);
+                clause.clear();
+                in_clause = false;
+                continue;
+            }
+            let var = lit.unsigned_abs() as usize;
+            if var > max_vars {
+                return Err(Error::parse(
+                    line_number,
+                    format!(
+                        "variable index {} is too large for an instance of {} bytes",
+                        var, max_vars
+                    ),
+                ));
+            }
+            new_vars(&mut instance, var);
+            let var = instance.vars[var - 1];
+            clause.push(if lit > 0 { var } else { !var });
+        }
+    }
+    if in_clause {
+        return Err(Error::parse(
+            text.lines().count(),
+            "the last clause is not terminated by 0",
+        ));
+    }
+    if weighted {
+        instance.model.minimize(objective);
+    }
+    Ok(instance)
+}
+
+/// Reads an instance file in the DIMACS CNF or WCNF format.
+pub fn read(path: impl AsRef<std::path::Path>) -> Result<DimacsModel, Error> {
+    parse(&std::fs::read_to_string(path)?)
+}
+
+fn parse_int(token: &str, line: usize) -> Result<i64, Error> {
+    token
+        .parse()
+        .map_err(|_| Error::parse(line, format!("invalid integer {}", token)))
+}
+
+fn new_vars(instance: &mut DimacsModel, num_vars: usize) {
+    while instance.vars.len() < num_vars {
+        let var = instance.model.new_bool_var();
+        instance.vars.push(var);
+    }
+}
+
+fn add_clause(
+    instance: &mut DimacsModel,
+    objective: &mut LinearExpr,
+    clause: &[BoolVar],
+    weight: Option<i64>,
+) {
+    let weight = match weight {
+        None => {
+            instance.model.add_or(clause.iter().copied());
+            return;
+        }
+        Some(weight) => weight,
+    };
+    let relaxation = match clause {
+        [] => {
+            *objective += weight;
+            return;
+        }
+        // A unit clause is violated iff its literal is false.
+        [lit] => !*lit,
+        _ => {
+            let relaxation = instance.model.new_bool_var();
+            instance
+                .model
+                .add_or(clause.iter().copied().chain(Some(relaxation)));
+            relaxation
+        }
+    };
+    *objective += (weight, relaxation);
+    instance
+        .soft_clauses
+        .push(SoftClause { relaxation, weight });
+}
+
+/// Writes a pure Boolean model in the DIMACS CNF format.
+///
+/// Variable `i` of the model is the DIMACS variable `i + 1`. Only
+/// `bool_or`, `bool_and`, `at_most_one` and `exactly_one` constraints
+/// and fixed Boolean variables are supported. Models with other
+/// constraints, non Boolean variables or an objective are rejected
+/// with [Error::Unsupported].
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::builder::CpModelBuilder;
+/// # use cp_sat::formats::dimacs;
+/// let mut model = CpModelBuilder::default();
+/// let x = model.new_bool_var();
+/// let y = model.new_bool_var();
+/// model.add_or([x, !y]);
+/// model.add_at_most_one([x, y]);
+/// let mut cnf = Vec::new();
+/// dimacs::write_cnf(&model, &mut cnf).unwrap();
+/// assert_eq!("p cnf 2 2\n1 -2 0\n-1 -2 0\n", String::from_utf8(cnf).unwrap());
+/// ```
+pub fn write_cnf(model: &CpModelBuilder, mut out: impl std::io::Write) -> Result<(), Error> {
+    let proto = model.proto();
+    if proto.objective.is_some() {
+        return Err(Error::Unsupported("the CNF format has no objective".into()));
+    }
+    let mut clauses: Vec<Vec<i32>> = Vec::new();
+    for (index, var) in proto.variables.iter().enumerate() {
+        let lit = index as i32;
+        match var.domain.as_slice() {
+            [0, 1] => {}
+            [0, 0] => clauses.push(vec![negated(lit)]),
+            [1, 1] => clauses.push(vec![lit]),
+            _ => {
+                return Err(Error::Unsupported(format!(
+                    "variable #{} is not Boolean",
+                    index
+                )))
+            }
+        }
+    }
+    for (index, cst) in proto.constraints.iter().enumerate() {
+        let negated_enforcement = || cst.enforcement_literal.iter().map(|&l| negated(l));
+        match &cst.constraint {
+            Some(CstEnum::BoolOr(arg)) => clauses.push(
+                negated_enforcement()
+                    .chain(arg.literals.iter().copied())
+                    .collect(),
+            ),
+            Some(CstEnum::BoolAnd(arg)) => {
+                for &lit in &arg.literals {
+                    clauses.push(negated_enforcement().chain(Some(lit)).collect());
+                }
+            }
+            Some(CstEnum::AtMostOne(arg)) | Some(CstEnum::ExactlyOne(arg))
+                if cst.enforcement_literal.is_empty() =>
+            {
+                if matches!(cst.constraint, Some(CstEnum::ExactlyOne(_))) {
+                    clauses.push(arg.literals.clone());
+                }
+                for (i, &a) in arg.literals.iter().enumerate() {
+                    for &b in &arg.literals[i + 1..] {
+                        clauses.push(vec![negated(a), negated(b)]);
+                    }
+                }
+            }
+            None => {}
+            Some(_) => {
+                return Err(Error::Unsupported(format!(
+                    "constraint #{} cannot be written as clauses",
+                    index
+                )))
+            }
+        }
+    }
+
+    writeln!(out, "p cnf {} {}", proto.variables.len(), clauses.len())?;
+    for clause in clauses {
+        for lit in clause {
+            // Negated references are `-index - 1`, DIMACS literals are
+            // `-(index + 1)`.
+            let var = var_index(lit) as i64 + 1;
+            write!(out, "{} ", if lit < 0 { -var } else { var })?;
+        }
+        writeln!(out, "0")?;
+    }
+    Ok(())
+}
+
+fn negated(lit: i32) -> i32 {
+    -lit - 1
+}
@@ -0,0 +1,1058 @@
+//! Reader for FlatZinc models, and writer of their solutions.
+//!
+//! Integer and Boolean parameters and variables, and arrays of them,
+//! are supported. The supported constraints are the linear and
+//! comparison constraints (`int_lin_*`, `int_eq`, `int_le`, ...,
+//! `bool_eq`, `bool_le`, ..., `set_in`) with their `_reif` and `_imp`
+//! variants, the Boolean constraints (`array_bool_or`,
+//! `array_bool_and`, `bool_clause`, ...), `int_times`, `int_div`,
+//! `int_mod`, `int_abs`, `int_min`, `int_max`, the element
+//! constraints, `all_different_int`, `cumulative` and `table_int`,
+//! optionally prefixed by `fzn_`. The `int_search` and `bool_search`
+//! annotations, possibly in a `seq_search`, are translated to decision
+//! strategies. Float and set variables are rejected with
+//! [Error::Unsupported].
+//!
+//! # Example
+//!
+//! ```
+//! # use cp_sat::formats::fzn;
+//! let text = "
+//! var 1..3: x :: output_var;
+//! var 1..3: y :: output_var;
+//! array [1..2] of var int: xs :: output_array([1..2]) = [x, y];
+//! constraint int_lin_le([1, 1], [x, y], 3);
+//! constraint fzn_all_different_int(xs);
+//! solve :: int_search(xs, input_order, indomain_max, complete) maximize x;
+//! ";
+//! let model = fzn::parse(text).unwrap();
+//! assert_eq!(2, model.model.proto().variables.len());
+//! assert_eq!(2, model.model.proto().constraints.len());
+//! assert_eq!(1, model.model.proto().search_strategy.len());
+//! ```
+
+use super::Error;
+use crate::builder::{
+    BoolVar, CpModelBuilder, DomainReduction, IntVar, LinearExpr, VariableSelection,
+};
+use crate::proto;
+use proto::constraint_proto::Constraint as CstEnum;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Bound of the domains of `var int` variables.
+const UNBOUNDED: i64 = 1 << 31;
+
+/// A FlatZinc model translated to CP SAT.
+#[derive(Debug)]
+pub struct FznModel {
+    /// The translated model.
+    pub model: CpModelBuilder,
+    outputs: Vec<Output>,
+    is_optimization: bool,
+}
+
+impl FznModel {
+    /// Returns the output of the response in the FlatZinc format: the
+    /// value of the output variables followed by `----------` if there
+    /// is a solution, and a status line such as `==========` if the
+    /// search is complete.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::formats::fzn;
+    /// # use cp_sat::proto::CpSolverStatus;
+    /// let model = fzn::parse("
+    /// var 1..10: x :: output_var;
+    /// array [1..2] of var bool: bs :: output_array([1..2]);
+    /// constraint int_le(x, 3);
+    /// solve maximize x;
+    /// ").unwrap();
+    /// let response = model.model.solve();
+    /// assert_eq!(response.status(), CpSolverStatus::Optimal);
+    /// assert!(model.output(&response).starts_with("x = 3;\nbs = array1d(1..2, ["));
+    /// assert!(model.output(&response).ends_with("]);\n----------\n==========\n"));
+    /// ```
+    pub fn output(&self, response: &proto::CpSolverResponse) -> String {
+        use proto::CpSolverStatus::*;
+        let mut res = String::new();
+        let status = response.status();
+        if status == Optimal || status == Feasible {
+            for output in &self.outputs {
+                let values: Vec<String> = output
+                    .values
+                    .iter()
+                    .map(|v| v.solution_value(response))
+                    .collect();
+                match &output.dims {
+                    None => res.push_str(&format!("{} = {};\n", output.name, values[0])),
+                    Some(dims) => {
+                        let dims: Vec<String> = dims
+                            .iter()
+                            .map(|(lb, ub)| format!("{}..{}", lb, ub))
+                            .collect();
+                        res.push_str(&format!(
+                            "{} = array{}d({}, [{}]);\n",
+                            output.name,
+                            dims.len(),
+                            dims.join(", "),
+                            values.join(", ")
+                        ));
+                    }
+                }
+            }
+            res.push_str("----------\n");
+        }
+        match status {
+            Optimal if self.is_optimization => res.push_str("==========\n"),
+            Infeasible => res.push_str("=====UNSATISFIABLE=====\n"),
+            Unknown => res.push_str("=====UNKNOWN=====\n"),
+            ModelInvalid => res.push_str("=====ERROR=====\n"),
+            _ => {}
+        }
+        res
+    }
+}
+
+/// Parses a model in the FlatZinc format.
+pub fn parse(text: &str) -> Result<FznModel, Error> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        pos: 0,
+        model: FznModel {
+            model: CpModelBuilder::default(),
+            outputs: Vec::new(),
+            is_optimization: false,
+        },
+        symbols: HashMap::new(),
+        constants: HashMap::new(),
+        bool_constants: [None, None],
+        array_vars_budget: text.len(),
+    };
+    while parser.pos < parser.tokens.len() {
+        parser.item()?;
+    }
+    Ok(parser.model)
+}
+
+/// Reads a model file in the FlatZinc format.
+pub fn read(path: impl AsRef<std::path::Path>) -> Result<FznModel, Error> {
+    parse(&std::fs::read_to_string(path)?)
+}
+
+#[derive(Debug)]
+struct Output {
+    name: String,
+    /// Index sets of an array, `None` for a scalar.
+    dims: Option<Vec<(i64, i64)>>,
+    values: Vec<Value>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Int(i64),
+    Float,
+    Str,
+    Punct(&'static str),
+}
+
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, Error> {
+    const PUNCTS: [&str; 12] = ["::", "..", ":", ";", ",", "[", "]", "(", ")", "{", "}", "="];
+    let mut tokens = Vec::new();
+    for (line_index, line) in text.lines().enumerate() {
+        let line_number = line_index + 1;
+        let mut rest = line;
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() || rest.starts_with('%') {
+                break;
+            }
+            let c = rest.chars().next().unwrap();
+            let (token, len) = if let Some(&punct) = PUNCTS.iter().find(|&&p| rest.starts_with(p)) {
+                (Token::Punct(punct), punct.len())
+            } else if c.is_ascii_alphabetic() || c == '_' {
+                let len = rest
+                    .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+                (Token::Ident(rest[..len].into()), len)
+            } else if c.is_ascii_digit() || c == '-' {
+                let digits = rest[1..]
+                    .find(|c: char| !c.is_ascii_digit())
+                    .map_or(rest.len(), |i| i + 1);
+                let after = &rest[digits..];
+                if (after.starts_with('.') && !after.starts_with(".."))
+                    || after.starts_with(['e', 'E'])
+                {
+                    let len = rest
+                        .find(|c: char| !c.is_ascii_alphanumeric() && !"+-.".contains(c))
+                        .unwrap_or(rest.len());
+                    (Token::Float, len)
+                } else {
+                    let value = rest[..digits].parse().map_err(|_| {
+                        Error::parse(line_number, format!("invalid integer {}", &rest[..digits]))
+                    })?;
+                    (Token::Int(value), digits)
+                }
+            } else if c == '"' {
+                let len = rest[1..]
+                    .find('"')
+                    .ok_or_else(|| Error::parse(line_number, "unterminated string"))?;
+                (Token::Str, len + 2)
+            } else {
+                return Err(Error::parse(
+                    line_number,
+                    format!("unexpected character {:?}", c),
+                ));
+            };
+            tokens.push((token, line_number));
+            rest = &rest[len..];
+        }
+    }
+    Ok(tokens)
+}
+
+/// A parsed expression.
+#[derive(Clone, Debug)]
+enum Expr {
+    Int(i64),
+    Float,
+    Str,
+    Ident(String),
+    Access(String, i64),
+    Array(Vec<Expr>),
+    Set(Vec<(i64, i64)>),
+    Call(String, Vec<Expr>),
+}
+
+/// The value of an expression.
+#[derive(Clone, Debug)]
+enum Value {
+    Int(i64),
+    Bool(bool),
+    Set(Vec<(i64, i64)>),
+    IntVar(IntVar),
+    BoolVar(BoolVar),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    fn solution_value(&self, response: &proto::CpSolverResponse) -> String {
+        match self {
+            Value::Int(v) => v.to_string(),
+            Value::Bool(b) => b.to_string(),
+            Value::IntVar(v) => v.solution_value(response).to_string(),
+            Value::BoolVar(v) => v.solution_value(response).to_string(),
+            Value::Set(set) => {
+                let set: Vec<String> = set.iter().map(|(l, u)| format!("{}..{}", l, u)).collect();
+                set.join(" union ")
+            }
+            Value::Array(values) => {
+                let values: Vec<String> =
+                    values.iter().map(|v| v.solution_value(response)).collect();
+                format!("[{}]", values.join(", "))
+            }
+        }
+    }
+}
+
+/// The linear constraint `expr in domain`.
+type LinearIn = (LinearExpr, Vec<(i64, i64)>);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Reif {
+    None,
+    Imp(BoolVar),
+    Iff(BoolVar),
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    model: FznModel,
+    symbols: HashMap<String, Value>,
+    constants: HashMap<i64, IntVar>,
+    bool_constants: [Option<BoolVar>; 2],
+    /// The number of variables that can still be created by array
+    /// declarations, bounded by the size of the input to avoid huge
+    /// allocations.
+    array_vars_budget: usize,
+}
+
+impl Parser {
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos.min(self.tokens.len().saturating_sub(1)))
+            .map_or(0, |&(_, line)| line)
+    }
+
+    fn error(&self, message: impl Into<String>) -> Error {
+        Error::parse(self.line(), message)
+    }
+
+    fn unsupported(&self, what: impl std::fmt::Display) -> Error {
+        Error::Unsupported(format!("line {}: {}", self.line(), what))
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn next(&mut self) -> Result<Token, Error> {
+        let token = self
+            .peek()
+            .cloned()
+            .ok_or_else(|| self.error("unexpected end of file"))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn is_punct(&self, punct: &str) -> bool {
+        matches!(self.peek(), Some(Token::Punct(p)) if *p == punct)
+    }
+
+    fn is_ident(&self, ident: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(i)) if i == ident)
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<(), Error> {
+        match self.next()? {
+            Token::Punct(p) if p == punct => Ok(()),
+            token => {
+                self.pos -= 1;
+                Err(self.error(format!("expected {}, got {:?}", punct, token)))
+            }
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, Error> {
+        match self.next()? {
+            Token::Ident(ident) => Ok(ident),
+            token => {
+                self.pos -= 1;
+                Err(self.error(format!("expected an identifier, got {:?}", token)))
+            }
+        }
+    }
+
+    fn int(&mut self) -> Result<i64, Error> {
+        match self.next()? {
+            Token::Int(value) => Ok(value),
+            token => {
+                self.pos -= 1;
+                Err(self.error(format!("expected an integer, got {:?}", token)))
+            }
+        }
+    }
+
+    /// Parses `lb..ub` or `{v1, v2, ...}`.
+    fn set(&mut self) -> Result<Vec<(i64, i64)>, Error> {
+        if self.is_punct("{") {
+            self.pos += 1;
+            let mut values = Vec::new();
+            while !self.is_punct("}") {
+                values.push(self.int()?);
+                if !self.is_punct("}") {
+                    self.expect(",")?;
+                }
+            }
+            self.pos += 1;
+            values.sort_unstable();
+            values.dedup();
+            let mut set: Vec<(i64, i64)> = Vec::new();
+            for v in values {
+                match set.last_mut() {
+                    Some(last) if last.1 + 1 == v => last.1 = v,
+                    _ => set.push((v, v)),
+                }
+            }
+            Ok(set)
+        } else {
+            let lb = self.int()?;
+            self.expect("..")?;
+            let ub = self.int()?;
+            Ok(if lb <= ub { vec![(lb, ub)] } else { Vec::new() })
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, Error> {
+        Ok(match self.next()? {
+            Token::Int(value) if self.is_punct("..") => {
+                self.pos += 1;
+                let ub = self.int()?;
+                Expr::Set(if value <= ub {
+                    vec![(value, ub)]
+                } else {
+                    Vec::new()
+                })
+            }
+            Token::Int(value) => Expr::Int(value),
+            Token::Float => Expr::Float,
+            Token::Str => Expr::Str,
+            Token::Punct("{") => {
+                self.pos -= 1;
+                Expr::Set(self.set()?)
+            }
+            Token::Punct("[") => {
+                let mut exprs = Vec::new();
+                while !self.is_punct("]") {
+                    exprs.push(self.expr()?);
+                    if !self.is_punct("]") {
+                        self.expect(",")?;
+                    }
+                }
+                self.pos += 1;
+                Expr::Array(exprs)
+            }
+            Token::Ident(ident) if self.is_punct("[") => {
+                self.pos += 1;
+                let index = self.int()?;
+                self.expect("]")?;
+                Expr::Access(ident, index)
+            }
+            Token::Ident(ident) if self.is_punct("(") => {
+                self.pos += 1;
+                let args = self.args()?;
+                Expr::Call(ident, args)
+            }
+            Token::Ident(ident) => Expr::Ident(ident),
+            token => {
+                self.pos -= 1;
+                return Err(self.error(format!("expected an expression, got {:?}", token)));
+            }
+        })
+    }
+
+    /// Parses the arguments of a call, after the opening parenthesis.
+    fn args(&mut self) -> Result<Vec<Expr>, Error> {
+        let mut args = Vec::new();
+        while !self.is_punct(")") {
+            args.push(self.expr()?);
+            if !self.is_punct(")") {
+                self.expect(",")?;
+            }
+        }
+        self.pos += 1;
+        Ok(args)
+    }
+
+    fn annotations(&mut self) -> Result<Vec<Expr>, Error> {
+        let mut annotations = Vec::new();
+        while self.is_punct("::") {
+            self.pos += 1;
+            annotations.push(self.expr()?);
+        }
+        Ok(annotations)
+    }
+
+    fn value(&self, expr: &Expr) -> Result<Value, Error> {
+        Ok(match expr {
+            Expr::Int(value) => Value::Int(*value),
+            Expr::Ident(ident) if ident == "true" => Value::Bool(true),
+            Expr::Ident(ident) if ident == "false" => Value::Bool(false),
+            Expr::Ident(ident) => self
+                .symbols
+                .get(ident)
+                .cloned()
+                .ok_or_else(|| self.error(format!("unknown identifier {}", ident)))?,
+            Expr::Access(ident, index) => match self.symbols.get(ident) {
+                Some(Value::Array(values)) => usize::try_from(*index - 1)
+                    .ok()
+                    .and_then(|i| values.get(i))
+                    .cloned()
+                    .ok_or_else(|| self.error(format!("index {} out of {}", index, ident)))?,
+                _ => return Err(self.error(format!("{} is not an array", ident))),
+            },
+            Expr::Array(exprs) => Value::Array(
+                exprs
+                    .iter()
+                    .map(|e| self.value(e))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Expr::Set(set) => Value::Set(set.clone()),
+            Expr::Float => return Err(self.unsupported("float values")),
+            Expr::Str | Expr::Call(..) => return Err(self.error("unexpected expression")),
+        })
+    }
+
+    fn item(&mut self) -> Result<(), Error> {
+        if self.is_ident("predicate") {
+            while !self.is_punct(";") {
+                self.next()?;
+            }
+            self.pos += 1;
+            return Ok(());
+        }
+        if self.is_ident("constraint") {
+            self.pos += 1;
+            let name = self.ident()?;
+            self.expect("(")?;
+            let args = self.args()?;
+            self.annotations()?;
+            let args = args
+                .iter()
+                .map(|a| self.value(a))
+                .collect::<Result<Vec<_>, _>>()?;
+            self.constraint(&name, &args)?;
+            return self.expect(";");
+        }
+        if self.is_ident("solve") {
+            self.pos += 1;
+            let annotations = self.annotations()?;
+            for annotation in &annotations {
+                self.search_annotation(annotation)?;
+            }
+            match self.ident()?.as_str() {
+                "satisfy" => {}
+                sense @ ("minimize" | "maximize") => {
+                    let expr = self.expr()?;
+                    let objective = self.value(&expr)?;
+                    let objective = self.linear(&objective)?;
+                    if sense == "minimize" {
+                        self.model.model.minimize(objective);
+                    } else {
+                        self.model.model.maximize(objective);
+                    }
+                    self.model.is_optimization = true;
+                }
+                sense => return Err(self.error(format!("unknown solve kind {}", sense))),
+            }
+            return self.expect(";");
+        }
+        self.declaration()
+    }
+
+    fn declaration(&mut self) -> Result<(), Error> {
+        let mut array_len = None;
+        if self.is_ident("array") {
+            self.pos += 1;
+            self.expect("[")?;
+            let index_set = self.set()?;
+            array_len = Some(match index_set.as_slice() {
+                [] => 0,
+                [(1, ub)] => {
+                    usize::try_from(*ub).map_err(|_| self.error("array index sets must be 1..n"))?
+                }
+                _ => return Err(self.error("array index sets must be 1..n")),
+            });
+            self.expect("]")?;
+            if self.ident()? != "of" {
+                return Err(self.error("expected of"));
+            }
+        }
+        let is_var = self.is_ident("var");
+        if is_var {
+            self.pos += 1;
+        }
+        // `None` for Booleans, the domain for integers.
+        let domain = match self.peek() {
+            Some(Token::Ident(ident)) if ident == "bool" => {
+                self.pos += 1;
+                None
+            }
+            Some(Token::Ident(ident)) if ident == "int" => {
+                self.pos += 1;
+                Some(vec![(-UNBOUNDED, UNBOUNDED)])
+            }
+            Some(Token::Ident(ident)) if ident == "float" => {
+                return Err(self.unsupported("float variables"))
+            }
+            Some(Token::Ident(ident)) if ident == "set" => {
+                self.pos += 1;
+                if self.ident()? != "of" {
+                    return Err(self.error("expected of"));
+                }
+                if is_var {
+                    return Err(self.unsupported("set variables"));
+                }
+                if self.is_ident("int") {
+                    self.pos += 1;
+                } else {
+                    self.set()?;
+                }
+                Some(Vec::new())
+            }
+            Some(Token::Punct(_)) | Some(Token::Int(_)) => Some(self.set()?),
+            token => return Err(self.error(format!("expected a type, got {:?}", token))),
+        };
+        self.expect(":")?;
+        let name = self.ident()?;
+        let annotations = self.annotations()?;
+        let value = if self.is_punct("=") {
+            self.pos += 1;
+            let expr = self.expr()?;
+            let value = self.value(&expr)?;
+            if let (Some(domain), Value::IntVar(var)) = (&domain, &value) {
+                if is_var && array_len.is_none() && domain[0] != (-UNBOUNDED, UNBOUNDED) {
+                    let domain = domain.clone();
+                    self.model.model.add_linear_constraint(*var, domain);
+                }
+            }
+            value
+        } else if !is_var {
+            return Err(self.error(format!("parameter {} has no value", name)));
+        } else {
+            let new_var = |model: &mut CpModelBuilder, name: String| match &domain {
+                None => Value::BoolVar(model.new_bool_var_with_name(name)),
+                Some(domain) => {
+                    Value::IntVar(model.new_int_var_with_name(domain.iter().copied(), name))
+                }
+            };
+            match array_len {
+                None => new_var(&mut self.model.model, name.clone()),
+                Some(len) if len > self.array_vars_budget => {
+                    return Err(self.error(format!(
+                        "array {} of {} variables is too large for the size of the input",
+                        name, len
+                    )))
+                }
+                Some(len) => {
+                    self.array_vars_budget -= len;
+                    Value::Array(
+                        (1..=len)
+                            .map(|i| new_var(&mut self.model.model, format!("{}[{}]", name, i)))
+                            .collect(),
+                    )
+                }
+            }
+        };
+
+        for annotation in &annotations {
+            let dims = match annotation {
+                Expr::Ident(ident) if ident == "output_var" => None,
+                Expr::Call(ident, args) if ident == "output_array" => match args.as_slice() {
+                    [Expr::Array(dims)] => Some(
+                        dims.iter()
+                            .map(|d| match d {
+                                Expr::Set(set) if set.len() == 1 => Ok(set[0]),
+                                _ => Err(self.error("invalid output_array dimensions")),
+                            })
+                            .collect::<Result<_, _>>()?,
+                    ),
+                    _ => return Err(self.error("invalid output_array dimensions")),
+                },
+                _ => continue,
+            };
+            let values = match &value {
+                Value::Array(values) => values.clone(),
+                value => vec![value.clone()],
+            };
+            self.model.outputs.push(Output {
+                name: name.clone(),
+                dims,
+                values,
+            });
+        }
+        self.symbols.insert(name, value);
+        self.expect(";")
+    }
+
+    fn search_annotation(&mut self, annotation: &Expr) -> Result<(), Error> {
+        let (name, args) = match annotation {
+            Expr::Call(name, args) => (name.as_str(), args),
+            _ => return Ok(()),
+        };
+        match (name, args.as_slice()) {
+            ("seq_search", [Expr::Array(annotations)]) => {
+                for annotation in annotations {
+                    self.search_annotation(annotation)?;
+                }
+            }
+            (
+                "int_search" | "bool_search",
+                [vars, Expr::Ident(var_sel), Expr::Ident(val_sel), ..],
+            ) => {
+                let variable_selection = match var_sel.as_str() {
+                    "smallest" => VariableSelection::ChooseLowestMin,
+                    "largest" => VariableSelection::ChooseHighestMax,
+                    "first_fail" => VariableSelection::ChooseMinDomainSize,
+                    "anti_first_fail" => VariableSelection::ChooseMaxDomainSize,
+                    _ => VariableSelection::ChooseFirst,
+                };
+                let domain_reduction = match val_sel.as_str() {
+                    "indomain_max" => DomainReduction::SelectMaxValue,
+                    "indomain_split" => DomainReduction::SelectLowerHalf,
+                    "indomain_reverse_split" => DomainReduction::SelectUpperHalf,
+                    "indomain_median" => DomainReduction::SelectMedianValue,
+                    _ => DomainReduction::SelectMinValue,
+                };
+                let vars = match self.value(vars)? {
+                    Value::Array(values) => values,
+                    value => vec![value],
+                };
+                let vars = vars.iter().filter_map(|v| match v {
+                    Value::IntVar(var) => Some(*var),
+                    Value::BoolVar(var) => Some((*var).into()),
+                    _ => None,
+                });
+                self.model
+                    .model
+                    .add_decision_strategy(vars, variable_selection, domain_reduction);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn int_value(&self, value: &Value) -> Result<i64, Error> {
+        match value {
+            Value::Int(v) => Ok(*v),
+            Value::Bool(b) => Ok(*b as i64),
+            _ => Err(self.error(format!("expected an integer, got {:?}", value))),
+        }
+    }
+
+    fn array<'a>(&self, value: &'a Value) -> Result<&'a [Value], Error> {
+        match value {
+            Value::Array(values) => Ok(values),
+            _ => Err(self.error(format!("expected an array, got {:?}", value))),
+        }
+    }
+
+    fn linear(&self, value: &Value) -> Result<LinearExpr, Error> {
+        Ok(match value {
+            Value::IntVar(var) => (*var).into(),
+            Value::BoolVar(var) => (*var).into(),
+            value => self.int_value(value)?.into(),
+        })
+    }
+
+    fn int_var(&mut self, value: &Value) -> Result<IntVar, Error> {
+        Ok(match value {
+            Value::IntVar(var) => *var,
+            Value::BoolVar(var) => (*var).into(),
+            value => {
+                let value = self.int_value(value)?;
+                let model = &mut self.model.model;
+                *self
+                    .constants
+                    .entry(value)
+                    .or_insert_with(|| model.new_int_var([(value, value)]))
+            }
+        })
+    }
+
+    fn bool_var(&mut self, value: &Value) -> Result<BoolVar, Error> {
+        Ok(match value {
+            Value::BoolVar(var) => *var,
+            Value::Bool(b) => match self.bool_constants[*b as usize] {
+                Some(var) => var,
+                None => {
+                    let var = self.model.model.new_bool_var();
+                    self.model.model.proto_mut().variables[var.0 as usize].domain =
+                        vec![*b as i64, *b as i64];
+                    self.bool_constants[*b as usize] = Some(var);
+                    var
+                }
+            },
+            _ => return Err(self.error(format!("expected a Boolean, got {:?}", value))),
+        })
+    }
+
+    fn bool_vars(&mut self, value: &Value) -> Result<Vec<BoolVar>, Error> {
+        let values = self.array(value)?;
+        values.iter().map(|v| self.bool_var(v)).collect()
+    }
+
+    fn int_vars(&mut self, value: &Value) -> Result<Vec<IntVar>, Error> {
+        let values = self.array(value)?;
+        values.iter().map(|v| self.int_var(v)).collect()
+    }
+
+    fn enforce(&mut self, cst: crate::builder::Constraint, lits: &[BoolVar]) {
+        self.model.model.proto_mut().constraints[cst.0].enforcement_literal =
+            lits.iter().map(|l| l.0).collect();
+    }
+
+    /// Adds `expr in domain`, reified by `reif`.
+    fn reified_linear(&mut self, expr: LinearExpr, domain: Vec<(i64, i64)>, reif: Reif) {
+        let model = &mut self.model.model;
+        match reif {
+            Reif::None => {
+                model.add_linear_constraint(expr, domain);
+            }
+            Reif::Imp(r) => {
+                let cst = model.add_linear_constraint(expr, domain);
+                self.enforce(cst, &[r]);
+            }
+            Reif::Iff(r) => {
+                let complement = complement(&domain);
+                let cst = model.add_linear_constraint(expr.clone(), domain);
+                self.enforce(cst, &[r]);
+                let cst = self.model.model.add_linear_constraint(expr, complement);
+                self.enforce(cst, &[!r]);
+            }
+        }
+    }
+
+    /// Adds `r <=> or(lits)`.
+    fn reified_or(&mut self, lits: Vec<BoolVar>, r: BoolVar) {
+        let model = &mut self.model.model;
+        for &lit in &lits {
+            model.add_or([!lit, r]);
+        }
+        model.add_or(lits.into_iter().chain(Some(!r)));
+    }
+
+    /// Returns `expr in domain` of the comparison constraints, or
+    /// `None` if the name is not a comparison.
+    fn comparison(&mut self, name: &str, args: &[Value]) -> Result<Option<LinearIn>, Error> {
+        let (expr, domain) = match (name, args) {
+            ("int_lin_eq" | "int_lin_le" | "int_lin_ne", [coeffs, vars, rhs]) => {
+                let coeffs = self.array(coeffs)?;
+                let vars = self.array(vars)?;
+                if coeffs.len() != vars.len() {
+                    return Err(self.error("coefficients and variables of different sizes"));
+                }
+                let mut expr = LinearExpr::default();
+                for (coeff, var) in coeffs.iter().zip(vars) {
+                    let coeff = self.int_value(coeff)?;
+                    expr += match var {
+                        Value::IntVar(var) => (coeff, *var).into(),
+                        Value::BoolVar(var) => (coeff, *var).into(),
+                        value => LinearExpr::from(coeff * self.int_value(value)?),
+                    };
+                }
+                let rhs = self.int_value(rhs)?;
+                let domain = match name {
+                    "int_lin_eq" => vec![(rhs, rhs)],
+                    "int_lin_le" => vec![(i64::MIN, rhs)],
+                    _ => complement(&[(rhs, rhs)]),
+                };
+                (expr, domain)
+            }
+            (
+                "int_eq" | "int_ne" | "int_le" | "int_lt" | "bool_eq" | "bool_ne" | "bool_le"
+                | "bool_lt" | "bool_xor" | "bool_not" | "bool2int",
+                [a, b],
+            ) => {
+                let expr = self.linear(a)? - self.linear(b)?;
+                let domain = match name {
+                    "int_eq" | "bool_eq" | "bool2int" => vec![(0, 0)],
+                    "int_le" | "bool_le" => vec![(i64::MIN, 0)],
+                    "int_lt" | "bool_lt" => vec![(i64::MIN, -1)],
+                    _ => complement(&[(0, 0)]),
+                };
+                (expr, domain)
+            }
+            ("set_in", [x, Value::Set(set)]) => (self.linear(x)?, set.clone()),
+            _ => return Ok(None),
+        };
+        Ok(Some((expr, domain)))
+    }
+
+    fn constraint(&mut self, name: &str, args: &[Value]) -> Result<(), Error> {
+        let name = name.strip_prefix("fzn_").unwrap_or(name);
+        // bool_xor(a, b, r) is the reified form of bool_xor(a, b).
+        let (base, reif) = if let Some(base) = name.strip_suffix("_reif") {
+            (base, true)
+        } else if name == "bool_xor" && args.len() == 3 {
+            (name, true)
+        } else {
+            (name, false)
+        };
+        let (base, imp) = match base.strip_suffix("_imp") {
+            Some(base) => (base, true),
+            None => (base, false),
+        };
+        if reif || imp {
+            if let Some((r, args)) = args.split_last() {
+                let r = self.bool_var(r)?;
+                if let Some((expr, domain)) = self.comparison(base, args)? {
+                    let reif = if imp { Reif::Imp(r) } else { Reif::Iff(r) };
+                    self.reified_linear(expr, domain, reif);
+                    return Ok(());
+                }
+            }
+        } else if let Some((expr, domain)) = self.comparison(name, args)? {
+            self.reified_linear(expr, domain, Reif::None);
+            return Ok(());
+        }
+
+        match (name, args) {
+            ("array_bool_or", [lits, r]) | ("array_bool_and", [lits, r]) => {
+                let mut lits = self.bool_vars(lits)?;
+                let r = self.bool_var(r)?;
+                if name == "array_bool_or" {
+                    self.reified_or(lits, r);
+                } else {
+                    // r <=> and(lits) is !r <=> or(!lits).
+                    lits.iter_mut().for_each(|l| *l = !*l);
+                    self.reified_or(lits, !r);
+                }
+            }
+            ("bool_or", [a, b, r]) | ("bool_and", [a, b, r]) => {
+                let array = Value::Array(vec![a.clone(), b.clone()]);
+                let name = format!("array_{}", name);
+                self.constraint(&name, &[array, r.clone()])?;
+            }
+            ("bool_clause", [pos, neg]) => {
+                let pos = self.bool_vars(pos)?;
+                let neg = self.bool_vars(neg)?;
+                self.model
+                    .model
+                    .add_or(pos.into_iter().chain(neg.into_iter().map(|l| !l)));
+            }
+            ("array_bool_xor", [lits]) => {
+                let lits = self.bool_vars(lits)?;
+                self.model.model.add_xor(lits);
+            }
+            ("int_plus", [a, b, c]) => {
+                let expr = self.linear(a)? + self.linear(b)? - self.linear(c)?;
+                self.model.model.add_eq(expr, 0);
+            }
+            ("int_times" | "int_div" | "int_mod", [a, b, c]) => {
+                let target = self.int_var(c)?.0;
+                let vars = vec![self.int_var(a)?.0, self.int_var(b)?.0];
+                let arg = proto::IntegerArgumentProto { target, vars };
+                self.model.model.add_cst(match name {
+                    "int_times" => CstEnum::IntProd(arg),
+                    "int_div" => CstEnum::IntDiv(arg),
+                    _ => CstEnum::IntMod(arg),
+                });
+            }
+            ("int_abs", [a, b]) => {
+                let a = self.linear(a)?;
+                let b = self.linear(b)?;
+                self.model.model.add_max_eq(b, [a.clone(), -a]);
+            }
+            ("int_min" | "int_max", [a, b, c]) => {
+                let exprs = [self.linear(a)?, self.linear(b)?];
+                let target = self.linear(c)?;
+                if name == "int_min" {
+                    self.model.model.add_min_eq(target, exprs);
+                } else {
+                    self.model.model.add_max_eq(target, exprs);
+                }
+            }
+            ("array_int_minimum" | "array_int_maximum", [target, exprs]) => {
+                let target = self.linear(target)?;
+                let exprs = self
+                    .array(exprs)?
+                    .iter()
+                    .map(|e| self.linear(e))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if name == "array_int_minimum" {
+                    self.model.model.add_min_eq(target, exprs);
+                } else {
+                    self.model.model.add_max_eq(target, exprs);
+                }
+            }
+            (
+                "array_int_element"
+                | "array_var_int_element"
+                | "array_bool_element"
+                | "array_var_bool_element",
+                [index, array, target],
+            ) => {
+                let index = self.int_var(index)?;
+                let mut vars = self.int_vars(array)?;
+                let len = vars.len() as i64;
+                // FlatZinc arrays start at 1, add a dummy first element.
+                vars.insert(0, vars.first().copied().unwrap_or(index));
+                self.model.model.add_linear_constraint(index, [(1, len)]);
+                let target = self.int_var(target)?;
+                self.model
+                    .model
+                    .add_cst(CstEnum::Element(proto::ElementConstraintProto {
+                        index: index.0,
+                        target: target.0,
+                        vars: vars.iter().map(|v| v.0).collect(),
+                    }));
+            }
+            ("all_different_int", [vars]) => {
+                let vars = self.int_vars(vars)?;
+                self.model.model.add_all_different(vars);
+            }
+            ("table_int" | "table_bool", [vars, values]) => {
+                let vars = self.int_vars(vars)?;
+                let values = self
+                    .array(values)?
+                    .iter()
+                    .map(|v| self.int_value(v))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if vars.is_empty() || values.len() % vars.len() != 0 {
+                    return Err(self.error("the table size is not a multiple of the arity"));
+                }
+                self.model
+                    .model
+                    .add_cst(CstEnum::Table(proto::TableConstraintProto {
+                        vars: vars.iter().map(|v| v.0).collect(),
+                        values,
+                        negated: false,
+                    }));
+            }
+            ("cumulative", [starts, durations, demands, capacity]) => {
+                let starts = self.array(starts)?.to_vec();
+                let durations = self.array(durations)?.to_vec();
+                let demands = self.int_vars(demands)?;
+                if starts.len() != durations.len() || starts.len() != demands.len() {
+                    return Err(self.error("cumulative arguments of different sizes"));
+                }
+                let capacity = self.int_var(capacity)?;
+                let mut intervals = Vec::with_capacity(starts.len());
+                for (start, duration) in starts.iter().zip(&durations) {
+                    intervals.push(self.interval(start, duration)?);
+                }
+                self.model
+                    .model
+                    .add_cst(CstEnum::Cumulative(proto::CumulativeConstraintProto {
+                        capacity: capacity.0,
+                        intervals,
+                        demands: demands.iter().map(|v| v.0).collect(),
+                    }));
+            }
+            _ => return Err(self.unsupported(format!("constraint {}", name))),
+        }
+        Ok(())
+    }
+
+    /// Adds an interval of the given start and duration, returns its
+    /// constraint index.
+    fn interval(&mut self, start: &Value, duration: &Value) -> Result<i32, Error> {
+        let start_var = self.int_var(start)?;
+        let size_var = self.int_var(duration)?;
+        let bounds = |var| {
+            self.model
+                .model
+                .var_bounds(var)
+                .ok_or_else(|| self.error("empty domain"))
+        };
+        let (start_lb, start_ub) = bounds(start_var)?;
+        let (size_lb, size_ub) = bounds(size_var)?;
+        let (end_lb, end_ub) = match (start_lb.checked_add(size_lb), start_ub.checked_add(size_ub))
+        {
+            (Some(lb), Some(ub)) => (lb, ub),
+            _ => return Err(self.error("the end of the interval overflows")),
+        };
+        let end = self.model.model.new_int_var([(end_lb, end_ub)]);
+        let cst = self
+            .model
+            .model
+            .add_cst(CstEnum::Interval(proto::IntervalConstraintProto {
+                start: start_var.0,
+                end: end.0,
+                size: size_var.0,
+                ..Default::default()
+            }));
+        Ok(cst.0 as i32)
+    }
+}
+
+/// Returns the complement of a sorted list of disjoint intervals.
+fn complement(domain: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    let mut res = Vec::new();
+    let mut next = i64::MIN;
+    for &(lb, ub) in domain {
+        if lb > next {
+            res.push((next, lb - 1));
+        }
+        if ub == i64::MAX {
+            return res;
+        }
+        next = ub + 1;
+    }
+    res.push((next, i64::MAX));
+    res
+}
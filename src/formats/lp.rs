@@ -0,0 +1,496 @@
+//! Reader for the CPLEX LP format of mixed integer programs.
+//!
+//! The objective, constraints (including ranged constraints),
+//! `Bounds`, `General` and `Binary` sections are supported.
+//! Semi-continuous variables, SOS, indicator and quadratic constraints
+//! are rejected with [Error::Unsupported]. Continuous columns are
+//! handled as described in [MipOptions].
+//!
+//! # Example
+//!
+//! ```
+//! # use cp_sat::formats::{lp, mip::MipOptions};
+//! # use cp_sat::proto::constraint_proto::Constraint;
+//! let lp = r"
+//! \ A small knapsack.
+//! Maximize
+//!  profit: 3 x + 2 y
+//! Subject To
+//!  capacity: 2 x + y <= 10
+//!  -3 <= x - y <= 3
+//! Bounds
+//!  x <= 4
+//!  y <= 4
+//! General
+//!  x y
+//! End
+//! ";
+//! let mip = lp::parse(lp, &MipOptions::default()).unwrap();
+//! assert_eq!(2, mip.columns.len());
+//! assert_eq!(2, mip.model.proto().constraints.len());
+//! assert_eq!("capacity", mip.model.proto().constraints[0].name);
+//! match &mip.model.proto().constraints[1].constraint {
+//!     Some(Constraint::Linear(linear)) => assert_eq!(vec![-3, 3], linear.domain),
+//!     _ => panic!("expected a linear constraint"),
+//! }
+//! ```
+
+use super::mip::{Mip, MipModel, MipOptions, Row};
+use super::Error;
+
+/// Parses a model in the CPLEX LP format.
+pub fn parse(text: &str, options: &MipOptions) -> Result<MipModel, Error> {
+    parse_mip(text)?.into_model(options)
+}
+
+/// Reads a model file in the CPLEX LP format.
+pub fn read(path: impl AsRef<std::path::Path>, options: &MipOptions) -> Result<MipModel, Error> {
+    parse(&std::fs::read_to_string(path)?, options)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Section {
+    None,
+    Objective,
+    Constraints,
+    Bounds,
+    General,
+    Binary,
+    End,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Le,
+    Ge,
+    Eq,
+    Plus,
+    Minus,
+    Colon,
+}
+
+impl Token {
+    fn is_comparison(&self) -> bool {
+        matches!(self, Token::Le | Token::Ge | Token::Eq)
+    }
+}
+
+/// Returns the section starting with the given lowercase words, the
+/// objective sense if it is the objective, and the number of words of
+/// the keyword.
+fn section_keyword(
+    words: &[&str],
+    maximize: &mut bool,
+    line: usize,
+) -> Result<Option<(Section, usize)>, Error> {
+    let first = match words.first() {
+        Some(first) => *first,
+        None => return Ok(None),
+    };
+    Ok(Some(match first {
+        "maximize" | "maximise" | "maximum" | "max" => {
+            *maximize = true;
+            (Section::Objective, 1)
+        }
+        "minimize" | "minimise" | "minimum" | "min" => {
+            *maximize = false;
+            (Section::Objective, 1)
+        }
+        "subject" if words.get(1) == Some(&"to") => (Section::Constraints, 2),
+        "such" if words.get(1) == Some(&"that") => (Section::Constraints, 2),
+        "st" | "s.t." | "st." => (Section::Constraints, 1),
+        "bounds" | "bound" => (Section::Bounds, 1),
+        "general" | "generals" | "gen" | "integer" | "integers" => (Section::General, 1),
+        "binary" | "binaries" | "bin" => (Section::Binary, 1),
+        "semi-continuous" | "semis" | "semi" | "sos" => {
+            return Err(Error::Unsupported(format!(
+                "line {}: {} section",
+                line, first
+            )))
+        }
+        "end" => (Section::End, 1),
+        _ => return Ok(None),
+    }))
+}
+
+fn parse_mip(text: &str) -> Result<Mip, Error> {
+    let mut mip = Mip::default();
+    let mut section = Section::None;
+    let mut objective = Vec::new();
+    let mut constraints = Vec::new();
+    let mut bounds = Vec::new();
+    let mut general = Vec::new();
+    let mut binary = Vec::new();
+
+    for (line_index, line) in text.lines().enumerate() {
+        let line_number = line_index + 1;
+        let mut line = match line.find('\\') {
+            Some(comment) => &line[..comment],
+            None => line,
+        };
+        let lower = line.to_ascii_lowercase();
+        let words: Vec<&str> = lower.split_whitespace().take(2).collect();
+        let mut maximize = mip.maximize;
+        if let Some((new_section, num_words)) = section_keyword(&words, &mut maximize, line_number)?
+        {
+            let mut rest = line.trim_start();
+            for _ in 0..num_words {
+                rest = rest[rest.find(char::is_whitespace).unwrap_or(rest.len())..].trim_start();
+            }
+            // A constraint can be named like a keyword.
+            if !rest.starts_with(':') {
+                section = new_section;
+                mip.maximize = maximize;
+                line = rest;
+            }
+        }
+
+        let tokens = match section {
+            Section::None | Section::End => {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                return Err(Error::parse(line_number, "data outside of a section"));
+            }
+            Section::Objective => &mut objective,
+            Section::Constraints => &mut constraints,
+            Section::Bounds => &mut bounds,
+            Section::General => &mut general,
+            Section::Binary => &mut binary,
+        };
+        tokenize(line, line_number, tokens)?;
+    }
+
+    let mut stream = Stream::new(&objective);
+    if let (Some(Token::Name(_)), Some(Token::Colon)) = (stream.peek(0), stream.peek(1)) {
+        stream.pos += 2;
+    }
+    let (terms, constant) = stream.expr(&mut mip)?;
+    if let Some(token) = stream.peek(0) {
+        return Err(stream.error(format!("unexpected {:?} in the objective", token)));
+    }
+    mip.objective = terms;
+    mip.objective_offset = constant;
+
+    let mut stream = Stream::new(&constraints);
+    while stream.peek(0).is_some() {
+        let row = stream.constraint(&mut mip)?;
+        mip.rows.push(row);
+    }
+
+    let mut stream = Stream::new(&bounds);
+    while stream.peek(0).is_some() {
+        stream.bound(&mut mip)?;
+    }
+
+    for (tokens, binary) in [(&general, false), (&binary, true)] {
+        for (token, line) in tokens {
+            let col = match token {
+                Token::Name(name) => mip.column(name),
+                _ => {
+                    return Err(Error::parse(
+                        *line,
+                        format!("expected a name, got {:?}", token),
+                    ))
+                }
+            };
+            let column = &mut mip.columns[col];
+            column.integer = true;
+            if binary {
+                column.lb = 0.;
+                column.ub = 1.;
+            }
+        }
+    }
+
+    Ok(mip)
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!\"#$%&()/,.;?@_`'{}|~".contains(c)
+}
+
+fn tokenize(line: &str, line_number: usize, tokens: &mut Vec<(Token, usize)>) -> Result<(), Error> {
+    let mut chars = line.char_indices().peekable();
+    while let Some((begin, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, c)| c);
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '+' => Token::Plus,
+            '-' if next == Some('>') => {
+                return Err(Error::Unsupported(format!(
+                    "line {}: indicator constraint",
+                    line_number
+                )))
+            }
+            '-' => Token::Minus,
+            ':' => Token::Colon,
+            '<' | '>' | '=' => {
+                let second = match next {
+                    Some(next @ ('<' | '>' | '=')) => {
+                        chars.next();
+                        Some(next)
+                    }
+                    _ => None,
+                };
+                match (c, second) {
+                    ('<', None) | ('<', Some('=')) | ('=', Some('<')) => Token::Le,
+                    ('>', None) | ('>', Some('=')) | ('=', Some('>')) => Token::Ge,
+                    ('=', None) | ('=', Some('=')) => Token::Eq,
+                    _ => return Err(Error::parse(line_number, "invalid comparison operator")),
+                }
+            }
+            '[' | '^' => {
+                return Err(Error::Unsupported(format!(
+                    "line {}: quadratic terms",
+                    line_number
+                )))
+            }
+            c if c.is_ascii_digit() || (c == '.' && next.is_some_and(|n| n.is_ascii_digit())) => {
+                let mut end = begin + 1;
+                let mut exponent = false;
+                while let Some(&(i, c)) = chars.peek() {
+                    let after = line[i + c.len_utf8()..].chars().next();
+                    let accepted = c.is_ascii_digit()
+                        || c == '.'
+                        || (!exponent
+                            && (c == 'e' || c == 'E')
+                            && after.is_some_and(|a| a.is_ascii_digit() || a == '+' || a == '-'));
+                    if !accepted {
+                        break;
+                    }
+                    if c == 'e' || c == 'E' {
+                        exponent = true;
+                        chars.next();
+                        if let Some(&(_, '+' | '-')) = chars.peek() {
+                            chars.next();
+                        }
+                    } else {
+                        chars.next();
+                    }
+                    end = chars.peek().map_or(line.len(), |&(i, _)| i);
+                }
+                let number = &line[begin..end];
+                Token::Number(
+                    number.parse().map_err(|_| {
+                        Error::parse(line_number, format!("invalid number {}", number))
+                    })?,
+                )
+            }
+            c if is_name_char(c) => {
+                let mut end = line.len();
+                while let Some(&(i, c)) = chars.peek() {
+                    if !is_name_char(c) {
+                        end = i;
+                        break;
+                    }
+                    chars.next();
+                }
+                Token::Name(line[begin..end].into())
+            }
+            c => {
+                return Err(Error::parse(
+                    line_number,
+                    format!("unexpected character {:?}", c),
+                ))
+            }
+        };
+        tokens.push((token, line_number));
+    }
+    Ok(())
+}
+
+struct Stream<'a> {
+    tokens: &'a [(Token, usize)],
+    pos: usize,
+}
+
+impl<'a> Stream<'a> {
+    fn new(tokens: &'a [(Token, usize)]) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    fn peek(&self, offset: usize) -> Option<&'a Token> {
+        self.tokens.get(self.pos + offset).map(|(t, _)| t)
+    }
+
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.peek(0);
+        self.pos += 1;
+        token
+    }
+
+    fn error(&self, message: impl Into<String>) -> Error {
+        let line = self
+            .tokens
+            .get(self.pos.min(self.tokens.len().saturating_sub(1)))
+            .map_or(0, |&(_, line)| line);
+        Error::parse(line, message)
+    }
+
+    /// Parses a linear expression, returns its terms and its constant.
+    fn expr(&mut self, mip: &mut Mip) -> Result<(Vec<(usize, f64)>, f64), Error> {
+        let mut terms = Vec::new();
+        let mut constant = 0.;
+        while let Some(token) = self.peek(0) {
+            if token.is_comparison() {
+                break;
+            }
+            let mut sign = 1.;
+            while let Some(token @ (Token::Plus | Token::Minus)) = self.peek(0) {
+                if *token == Token::Minus {
+                    sign = -sign;
+                }
+                self.pos += 1;
+            }
+            match (self.next(), self.peek(0)) {
+                (Some(Token::Number(coeff)), Some(Token::Name(name))) => {
+                    self.pos += 1;
+                    terms.push((mip.column(name), sign * coeff));
+                }
+                (Some(Token::Number(value)), _) => constant += sign * value,
+                (Some(Token::Name(name)), _) => terms.push((mip.column(name), sign)),
+                (token, _) => {
+                    self.pos -= 1;
+                    return Err(self.error(format!("expected a term, got {:?}", token)));
+                }
+            }
+        }
+        Ok((terms, constant))
+    }
+
+    /// Returns the value of the signed constant, possibly infinite, at
+    /// the current position and the number of its tokens.
+    fn constant_at(&self) -> Option<(f64, usize)> {
+        let mut sign = 1.;
+        let mut len = 0;
+        loop {
+            match self.peek(len)? {
+                Token::Plus => {}
+                Token::Minus => sign = -sign,
+                Token::Number(value) => return Some((sign * value, len + 1)),
+                Token::Name(name)
+                    if name.eq_ignore_ascii_case("inf")
+                        || name.eq_ignore_ascii_case("infinity") =>
+                {
+                    return Some((sign * f64::INFINITY, len + 1))
+                }
+                _ => return None,
+            }
+            len += 1;
+        }
+    }
+
+    fn constant(&mut self) -> Result<f64, Error> {
+        match self.constant_at() {
+            Some((value, len)) => {
+                self.pos += len;
+                Ok(value)
+            }
+            None => Err(self.error(format!("expected a number, got {:?}", self.peek(0)))),
+        }
+    }
+
+    /// Returns the comparison operator at the current position, and
+    /// moves past it.
+    fn comparison(&mut self) -> Result<&'a Token, Error> {
+        match self.peek(0) {
+            Some(token) if token.is_comparison() => {
+                self.pos += 1;
+                Ok(token)
+            }
+            token => Err(self.error(format!("expected a comparison, got {:?}", token))),
+        }
+    }
+
+    fn constraint(&mut self, mip: &mut Mip) -> Result<Row, Error> {
+        let name = match (self.peek(0), self.peek(1)) {
+            (Some(Token::Name(name)), Some(Token::Colon)) => {
+                self.pos += 2;
+                name.clone()
+            }
+            _ => format!("R{}", mip.rows.len() + 1),
+        };
+        let mut row = Row {
+            name,
+            lb: -f64::INFINITY,
+            ub: f64::INFINITY,
+            ..Default::default()
+        };
+        let left = match self.constant_at() {
+            Some((value, len)) if self.peek(len).is_some_and(Token::is_comparison) => {
+                self.pos += len;
+                Some((value, self.comparison()?))
+            }
+            _ => None,
+        };
+        let (terms, constant) = self.expr(mip)?;
+        let op = self.comparison()?;
+        let right = self.constant()?;
+        set_bounds(&mut row.lb, &mut row.ub, op, right, false);
+        if let Some((value, left_op)) = left {
+            if left_op != op || *op == Token::Eq {
+                return Err(self.error("invalid ranged constraint"));
+            }
+            set_bounds(&mut row.lb, &mut row.ub, left_op, value, true);
+        }
+        row.terms = terms;
+        row.lb -= constant;
+        row.ub -= constant;
+        Ok(row)
+    }
+
+    fn bound(&mut self, mip: &mut Mip) -> Result<(), Error> {
+        let (name, left) = match (self.peek(0), self.peek(1)) {
+            (Some(Token::Name(name)), Some(Token::Name(free)))
+                if free.eq_ignore_ascii_case("free") =>
+            {
+                self.pos += 2;
+                let col = mip.column(name);
+                let column = &mut mip.columns[col];
+                column.lb = -f64::INFINITY;
+                column.ub = f64::INFINITY;
+                return Ok(());
+            }
+            (Some(Token::Name(name)), Some(token)) if token.is_comparison() => {
+                self.pos += 1;
+                (name, None)
+            }
+            _ => {
+                let value = self.constant()?;
+                let op = self.comparison()?;
+                match self.next() {
+                    Some(Token::Name(name)) => (name, Some((value, op))),
+                    token => return Err(self.error(format!("expected a name, got {:?}", token))),
+                }
+            }
+        };
+        let col = mip.column(name);
+        let (mut lb, mut ub) = (mip.columns[col].lb, mip.columns[col].ub);
+        if let Some((value, op)) = left {
+            set_bounds(&mut lb, &mut ub, op, value, true);
+        }
+        if left.is_none() || self.peek(0).is_some_and(Token::is_comparison) {
+            let op = self.comparison()?;
+            let value = self.constant()?;
+            set_bounds(&mut lb, &mut ub, op, value, false);
+        }
+        mip.columns[col].lb = lb;
+        mip.columns[col].ub = ub;
+        Ok(())
+    }
+}
+
+/// Applies `x op value`, or `value op x` if `reversed`.
+fn set_bounds(lb: &mut f64, ub: &mut f64, op: &Token, value: f64, reversed: bool) {
+    match (op, reversed) {
+        (Token::Le, false) | (Token::Ge, true) => *ub = value,
+        (Token::Ge, false) | (Token::Le, true) => *lb = value,
+        _ => {
+            *lb = value;
+            *ub = value;
+        }
+    }
+}
@@ -0,0 +1,247 @@
+//! Mixed integer programs, shared by the [MPS][super::mps] and
+//! [LP][super::lp] readers.
+
+use super::Error;
+use crate::builder::{CpModelBuilder, IntVar, LinearExpr};
+use crate::numeric::integer_multiplier;
+use crate::proto;
+use std::collections::HashMap;
+
+/// Options for the translation of a mixed integer program to CP SAT.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MipOptions {
+    /// Continuous columns are rejected with [Error::Unsupported] if
+    /// `None`. Otherwise, a continuous column `x` is represented by
+    /// an integer variable equal to `x * continuous_scale`, i.e. `x`
+    /// is discretized with a step of `1 / continuous_scale`, which
+    /// must be positive.
+    pub continuous_scale: Option<i64>,
+    /// Bound used in place of infinite column bounds, as CP SAT needs
+    /// bounded variables. Defaults to 10^7, as the `mip_max_bound`
+    /// parameter of CP SAT.
+    pub infinity: i64,
+    /// Fractional coefficients are scaled by a power of 10 to become
+    /// integers. This is the maximum exponent allowed, defaults to 9.
+    pub max_scaling_digits: u32,
+}
+
+impl Default for MipOptions {
+    fn default() -> Self {
+        Self {
+            continuous_scale: None,
+            infinity: 10_000_000,
+            max_scaling_digits: 9,
+        }
+    }
+}
+
+/// A column of a mixed integer program, and its corresponding
+/// variable in the [CpModelBuilder].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MipColumn {
+    /// The name of the column.
+    pub name: String,
+    /// The variable representing the column.
+    pub var: IntVar,
+    /// The value of the column is the value of `var` divided by
+    /// `scale`. It is 1 for integer columns, and
+    /// [MipOptions::continuous_scale] for continuous columns.
+    pub scale: i64,
+}
+
+impl MipColumn {
+    /// Returns the value of the column in the solution of the
+    /// response.
+    pub fn solution_value(&self, response: &proto::CpSolverResponse) -> f64 {
+        self.var.solution_value(response) as f64 / self.scale as f64
+    }
+}
+
+/// A mixed integer program translated to CP SAT.
+#[derive(Debug)]
+pub struct MipModel {
+    /// The translated model.
+    pub model: CpModelBuilder,
+    /// The columns of the program, in order of appearance.
+    pub columns: Vec<MipColumn>,
+}
+
+impl MipModel {
+    /// Returns the column with the given name.
+    pub fn column(&self, name: &str) -> Option<&MipColumn> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+}
+
+/// Intermediate representation of a mixed integer program, with
+/// floating point data.
+#[derive(Debug, Default)]
+pub(crate) struct Mip {
+    pub(crate) name: String,
+    pub(crate) maximize: bool,
+    pub(crate) objective: Vec<(usize, f64)>,
+    pub(crate) objective_offset: f64,
+    pub(crate) columns: Vec<Column>,
+    pub(crate) column_index: HashMap<String, usize>,
+    pub(crate) rows: Vec<Row>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Column {
+    pub(crate) name: String,
+    pub(crate) lb: f64,
+    pub(crate) ub: f64,
+    pub(crate) integer: bool,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Row {
+    pub(crate) name: String,
+    pub(crate) terms: Vec<(usize, f64)>,
+    pub(crate) lb: f64,
+    pub(crate) ub: f64,
+}
+
+/// Values with an absolute value above this threshold are infinite.
+pub(crate) const INFINITY: f64 = 1e20;
+
+impl Mip {
+    /// Returns the index of the column, creating a continuous column
+    /// in [0, +inf) if it does not exist yet.
+    pub(crate) fn column(&mut self, name: &str) -> usize {
+        if let Some(&index) = self.column_index.get(name) {
+            return index;
+        }
+        let index = self.columns.len();
+        self.columns.push(Column {
+            name: name.into(),
+            lb: 0.,
+            ub: f64::INFINITY,
+            integer: false,
+        });
+        self.column_index.insert(name.into(), index);
+        index
+    }
+
+    pub(crate) fn into_model(self, options: &MipOptions) -> Result<MipModel, Error> {
+        if let Some(scale) = options.continuous_scale.filter(|&s| s <= 0) {
+            return Err(Error::Unsupported(format!(
+                "MipOptions::continuous_scale must be positive, got {}",
+                scale
+            )));
+        }
+        let mut model = CpModelBuilder::default();
+        model.proto_mut().name = self.name;
+        let mut columns = Vec::with_capacity(self.columns.len());
+        for column in self.columns {
+            let scale = match (column.integer, options.continuous_scale) {
+                (true, _) => 1,
+                (false, Some(scale)) => scale,
+                (false, None) => {
+                    return Err(Error::Unsupported(format!(
+                    "column {} is continuous, set MipOptions::continuous_scale to discretize it",
+                    column.name
+                )))
+                }
+            };
+            let lb = if column.lb <= -INFINITY {
+                -options.infinity
+            } else {
+                to_i64((column.lb * scale as f64 - 1e-6).ceil(), &column.name)?
+            };
+            let ub = if column.ub >= INFINITY {
+                options.infinity
+            } else {
+                to_i64((column.ub * scale as f64 + 1e-6).floor(), &column.name)?
+            };
+            if lb > ub {
+                return Err(Error::Unsupported(format!(
+                    "column {} has an empty domain",
+                    column.name
+                )));
+            }
+            let var = model.new_int_var_with_name([(lb, ub)], column.name.clone());
+            columns.push(MipColumn {
+                name: column.name,
+                var,
+                scale,
+            });
+        }
+
+        for row in self.rows {
+            let coeffs: Vec<f64> = row
+                .terms
+                .iter()
+                .map(|&(col, coeff)| coeff / columns[col].scale as f64)
+                .collect();
+            let multiplier =
+                integer_multiplier(&coeffs, options.max_scaling_digits).ok_or_else(|| {
+                    Error::Unsupported(format!(
+                        "row {} has coefficients that cannot be scaled to integers",
+                        row.name
+                    ))
+                })?;
+            let expr: LinearExpr = row
+                .terms
+                .iter()
+                .zip(&coeffs)
+                .map(|(&(col, _), &coeff)| ((coeff * multiplier).round() as i64, columns[col].var))
+                .collect();
+            let lb = if row.lb <= -INFINITY {
+                i64::MIN
+            } else {
+                to_i64((row.lb * multiplier - 1e-6).ceil(), &row.name)?
+            };
+            let ub = if row.ub >= INFINITY {
+                i64::MAX
+            } else {
+                to_i64((row.ub * multiplier + 1e-6).floor(), &row.name)?
+            };
+            let cst = model.add_linear_constraint(expr, [(lb, ub)]);
+            model.set_constraint_name(cst, &row.name);
+        }
+
+        if !self.objective.is_empty() || self.objective_offset != 0. {
+            let coeffs: Vec<f64> = self
+                .objective
+                .iter()
+                .map(|&(col, coeff)| coeff / columns[col].scale as f64)
+                .collect();
+            let multiplier =
+                integer_multiplier(&coeffs, options.max_scaling_digits).ok_or_else(|| {
+                    Error::Unsupported(
+                        "the objective has coefficients that cannot be scaled to integers".into(),
+                    )
+                })?;
+            let expr: LinearExpr = self
+                .objective
+                .iter()
+                .zip(&coeffs)
+                .map(|(&(col, _), &coeff)| ((coeff * multiplier).round() as i64, columns[col].var))
+                .collect();
+            let sign = if self.maximize {
+                model.maximize(expr);
+                -1.
+            } else {
+                model.minimize(expr);
+                1.
+            };
+            let objective = model.proto_mut().objective.as_mut().unwrap();
+            objective.offset = sign * self.objective_offset * multiplier;
+            objective.scaling_factor = sign / multiplier;
+        }
+
+        Ok(MipModel { model, columns })
+    }
+}
+
+fn to_i64(value: f64, name: &str) -> Result<i64, Error> {
+    if value.abs() < 2f64.powi(62) {
+        Ok(value as i64)
+    } else {
+        Err(Error::Unsupported(format!(
+            "{} has a bound too large for a 64-bit integer",
+            name
+        )))
+    }
+}
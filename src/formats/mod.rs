@@ -0,0 +1,57 @@
+pub mod dimacs;
+pub mod fzn;
+pub mod lp;
+pub mod mip;
+pub mod mps;
+pub mod opb;
+
+/// An error while reading a model file.
+#[derive(Debug)]
+pub enum Error {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The file is not valid, `line` starts at 1.
+    Parse {
+        /// The line of the problem.
+        line: usize,
+        /// A human-readable description of the problem.
+        message: String,
+    },
+    /// The file is valid, but uses a feature that cannot be
+    /// translated to CP SAT.
+    Unsupported(String),
+}
+
+impl Error {
+    pub(crate) fn parse(line: usize, message: impl Into<String>) -> Self {
+        Self::Parse {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            Self::Unsupported(message) => write!(f, "unsupported: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
@@ -0,0 +1,384 @@
+//! Reader for the MPS format of mixed integer programs.
+//!
+//! Both the free format, where fields are separated by whitespace,
+//! and the fixed format, where fields are at fixed columns and names
+//! can contain spaces, are supported. Integer columns are declared
+//! between `'MARKER'` lines, or with `BV`, `LI` and `UI` bounds.
+//! Continuous columns are handled as described in [MipOptions].
+//!
+//! # Example
+//!
+//! ```
+//! # use cp_sat::formats::{mip::MipOptions, mps};
+//! let mps = "\
+//! NAME          EXAMPLE
+//! OBJSENSE
+//!     MAX
+//! ROWS
+//!  N  profit
+//!  L  capacity
+//! COLUMNS
+//!     MARKER                 'MARKER'                 'INTORG'
+//!     x         profit       3              capacity     2
+//!     y         profit       2              capacity     1
+//!     MARKER                 'MARKER'                 'INTEND'
+//! RHS
+//!     RHS       capacity     10
+//! BOUNDS
+//!  UP BND       x            4
+//!  UP BND       y            4
+//! ENDATA
+//! ";
+//! let mip = mps::parse(mps, &MipOptions::default()).unwrap();
+//! assert_eq!(2, mip.columns.len());
+//! assert_eq!(1, mip.model.proto().constraints.len());
+//! let x = mip.column("x").unwrap();
+//! assert_eq!(vec![0, 4], mip.model.proto().variables[0].domain);
+//! assert_eq!(1, x.scale);
+//! ```
+
+use super::mip::{Mip, MipModel, MipOptions, Row, INFINITY};
+use super::Error;
+use std::collections::HashMap;
+
+/// Parses a model in the free MPS format.
+pub fn parse(text: &str, options: &MipOptions) -> Result<MipModel, Error> {
+    Parser::default().parse(text, false)?.into_model(options)
+}
+
+/// Parses a model in the fixed MPS format.
+pub fn parse_fixed(text: &str, options: &MipOptions) -> Result<MipModel, Error> {
+    Parser::default().parse(text, true)?.into_model(options)
+}
+
+/// Reads a model file in the free MPS format.
+pub fn read(path: impl AsRef<std::path::Path>, options: &MipOptions) -> Result<MipModel, Error> {
+    parse(&std::fs::read_to_string(path)?, options)
+}
+
+/// Reads a model file in the fixed MPS format.
+pub fn read_fixed(
+    path: impl AsRef<std::path::Path>,
+    options: &MipOptions,
+) -> Result<MipModel, Error> {
+    parse_fixed(&std::fs::read_to_string(path)?, options)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Section {
+    None,
+    ObjSense,
+    Rows,
+    Columns,
+    Rhs,
+    Ranges,
+    Bounds,
+    End,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum RowType {
+    Equal,
+    Less,
+    Greater,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum RowRef {
+    Objective,
+    /// Free row which is not the objective.
+    Ignored,
+    Row(usize),
+}
+
+#[derive(Default)]
+struct Parser {
+    mip: Mip,
+    rows: HashMap<String, RowRef>,
+    /// Type, right hand side and range of the rows.
+    row_data: Vec<(RowType, f64, Option<f64>)>,
+    has_objective: bool,
+}
+
+impl Parser {
+    fn parse(mut self, text: &str, fixed: bool) -> Result<Mip, Error> {
+        let mut section = Section::None;
+        let mut integer = false;
+        for (line_index, line) in text.lines().enumerate() {
+            let line_number = line_index + 1;
+            if line.trim().is_empty() || line.starts_with('*') {
+                continue;
+            }
+            if !line.starts_with(char::is_whitespace) {
+                let mut tokens = line.split_whitespace();
+                let keyword = tokens.next().unwrap().to_ascii_uppercase();
+                section = match keyword.as_str() {
+                    "NAME" => {
+                        self.mip.name = line[4..].trim().into();
+                        Section::None
+                    }
+                    "OBJSENSE" => match tokens.next() {
+                        Some(sense) => {
+                            self.objective_sense(sense, line_number)?;
+                            Section::None
+                        }
+                        None => Section::ObjSense,
+                    },
+                    "ROWS" => Section::Rows,
+                    "COLUMNS" => Section::Columns,
+                    "RHS" => Section::Rhs,
+                    "RANGES" => Section::Ranges,
+                    "BOUNDS" => Section::Bounds,
+                    "ENDATA" => Section::End,
+                    "SOS" | "QUADOBJ" | "QMATRIX" | "QSECTION" | "QCMATRIX" | "INDICATORS" => {
+                        return Err(Error::Unsupported(format!(
+                            "line {}: {} section",
+                            line_number, keyword
+                        )))
+                    }
+                    _ => {
+                        return Err(Error::parse(
+                            line_number,
+                            format!("unknown section {}", keyword),
+                        ))
+                    }
+                };
+                continue;
+            }
+
+            let fields = if fixed {
+                fixed_fields(line)
+            } else {
+                line.split_whitespace().collect()
+            };
+            if fields.is_empty() {
+                return Err(Error::parse(line_number, "missing field"));
+            }
+            match section {
+                Section::None | Section::End => {
+                    return Err(Error::parse(line_number, "data outside of a section"))
+                }
+                Section::ObjSense => self.objective_sense(fields[0], line_number)?,
+                Section::Rows => self.row(&fields, line_number)?,
+                Section::Columns => {
+                    // Markers do not follow the fixed format columns.
+                    if line.contains("'MARKER'") {
+                        if line.contains("'INTORG'") {
+                            integer = true;
+                        } else if line.contains("'INTEND'") {
+                            integer = false;
+                        } else {
+                            return Err(Error::parse(line_number, "invalid marker"));
+                        }
+                    } else {
+                        self.column(&fields, integer, line_number)?;
+                    }
+                }
+                Section::Rhs => self.rhs_or_range(&fields, false, line_number)?,
+                Section::Ranges => self.rhs_or_range(&fields, true, line_number)?,
+                Section::Bounds => self.bound(&fields, line_number)?,
+            }
+        }
+
+        for (index, (row_type, rhs, range)) in self.row_data.iter().copied().enumerate() {
+            let row = &mut self.mip.rows[index];
+            let (lb, ub) = match (row_type, range) {
+                (RowType::Equal, None) => (rhs, rhs),
+                (RowType::Equal, Some(r)) if r < 0. => (rhs + r, rhs),
+                (RowType::Equal, Some(r)) => (rhs, rhs + r),
+                (RowType::Less, None) => (-f64::INFINITY, rhs),
+                (RowType::Less, Some(r)) => (rhs - r.abs(), rhs),
+                (RowType::Greater, None) => (rhs, f64::INFINITY),
+                (RowType::Greater, Some(r)) => (rhs, rhs + r.abs()),
+            };
+            row.lb = lb;
+            row.ub = ub;
+        }
+        Ok(self.mip)
+    }
+
+    fn objective_sense(&mut self, sense: &str, line: usize) -> Result<(), Error> {
+        self.mip.maximize = match sense.to_ascii_uppercase().as_str() {
+            "MAX" | "MAXIMIZE" => true,
+            "MIN" | "MINIMIZE" => false,
+            _ => {
+                return Err(Error::parse(
+                    line,
+                    format!("unknown objective sense {}", sense),
+                ))
+            }
+        };
+        Ok(())
+    }
+
+    fn row(&mut self, fields: &[&str], line: usize) -> Result<(), Error> {
+        let (row_type, name) = match fields {
+            [row_type, name] => (row_type.to_ascii_uppercase(), *name),
+            _ => return Err(Error::parse(line, "expected a row type and a row name")),
+        };
+        let row_ref = match row_type.as_str() {
+            "N" if !self.has_objective => {
+                self.has_objective = true;
+                RowRef::Objective
+            }
+            "N" => RowRef::Ignored,
+            "E" | "L" | "G" => {
+                self.row_data.push((
+                    match row_type.as_str() {
+                        "E" => RowType::Equal,
+                        "L" => RowType::Less,
+                        _ => RowType::Greater,
+                    },
+                    0.,
+                    None,
+                ));
+                self.mip.rows.push(Row {
+                    name: name.into(),
+                    ..Default::default()
+                });
+                RowRef::Row(self.mip.rows.len() - 1)
+            }
+            _ => return Err(Error::parse(line, format!("unknown row type {}", row_type))),
+        };
+        if self.rows.insert(name.into(), row_ref).is_some() {
+            return Err(Error::parse(line, format!("duplicate row {}", name)));
+        }
+        Ok(())
+    }
+
+    fn row_ref(&self, name: &str, line: usize) -> Result<RowRef, Error> {
+        self.rows
+            .get(name)
+            .copied()
+            .ok_or_else(|| Error::parse(line, format!("unknown row {}", name)))
+    }
+
+    fn column(&mut self, fields: &[&str], integer: bool, line: usize) -> Result<(), Error> {
+        if fields.len() != 3 && fields.len() != 5 {
+            return Err(Error::parse(
+                line,
+                "expected a column name and one or two (row, value) pairs",
+            ));
+        }
+        let col = self.mip.column(fields[0]);
+        self.mip.columns[col].integer |= integer;
+        for pair in fields[1..].chunks(2) {
+            let value = parse_number(pair[1], line)?;
+            match self.row_ref(pair[0], line)? {
+                RowRef::Objective => self.mip.objective.push((col, value)),
+                RowRef::Ignored => {}
+                RowRef::Row(row) => self.mip.rows[row].terms.push((col, value)),
+            }
+        }
+        Ok(())
+    }
+
+    fn rhs_or_range(&mut self, fields: &[&str], range: bool, line: usize) -> Result<(), Error> {
+        // The name of the RHS or RANGES vector is optional.
+        let pairs = match fields.len() {
+            2 | 4 => fields,
+            3 | 5 => &fields[1..],
+            _ => return Err(Error::parse(line, "expected one or two (row, value) pairs")),
+        };
+        for pair in pairs.chunks(2) {
+            let value = parse_number(pair[1], line)?;
+            match (self.row_ref(pair[0], line)?, range) {
+                (RowRef::Objective, false) => self.mip.objective_offset = -value,
+                (RowRef::Row(row), false) => self.row_data[row].1 = value,
+                (RowRef::Row(row), true) => self.row_data[row].2 = Some(value),
+                (RowRef::Objective, true) | (RowRef::Ignored, _) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn bound(&mut self, fields: &[&str], line: usize) -> Result<(), Error> {
+        let bound_type = fields[0].to_ascii_uppercase();
+        let needs_value = !matches!(bound_type.as_str(), "FR" | "MI" | "PL" | "BV");
+        // The name of the BOUNDS vector is optional.
+        let (name, value) = match (fields.len(), needs_value) {
+            (2, false) => (fields[1], None),
+            (3, false) if self.mip.column_index.contains_key(fields[2]) => (fields[2], None),
+            (3, _) => (fields[1], Some(fields[2])),
+            (4, _) => (fields[2], Some(fields[3])),
+            _ => return Err(Error::parse(line, "invalid bound")),
+        };
+        let value = value.map(|v| parse_number(v, line)).transpose()?;
+        let col = *self
+            .mip
+            .column_index
+            .get(name)
+            .ok_or_else(|| Error::parse(line, format!("unknown column {}", name)))?;
+        let column = &mut self.mip.columns[col];
+        let value = value.unwrap_or(0.);
+        match bound_type.as_str() {
+            "UP" => column.ub = value,
+            "LO" => column.lb = value,
+            "FX" => {
+                column.lb = value;
+                column.ub = value;
+            }
+            "FR" => {
+                column.lb = -f64::INFINITY;
+                column.ub = f64::INFINITY;
+            }
+            "MI" => column.lb = -f64::INFINITY,
+            "PL" => column.ub = f64::INFINITY,
+            "BV" => {
+                column.integer = true;
+                column.lb = 0.;
+                column.ub = 1.;
+            }
+            "LI" => {
+                column.integer = true;
+                column.lb = value;
+            }
+            "UI" => {
+                column.integer = true;
+                column.ub = value;
+            }
+            "SC" => {
+                return Err(Error::Unsupported(format!(
+                    "line {}: semi-continuous column {}",
+                    line, name
+                )))
+            }
+            _ => {
+                return Err(Error::parse(
+                    line,
+                    format!("unknown bound type {}", bound_type),
+                ))
+            }
+        }
+        if column.lb <= -INFINITY {
+            column.lb = -f64::INFINITY;
+        }
+        if column.ub >= INFINITY {
+            column.ub = f64::INFINITY;
+        }
+        Ok(())
+    }
+}
+
+fn parse_number(s: &str, line: usize) -> Result<f64, Error> {
+    s.parse()
+        .map_err(|_| Error::parse(line, format!("invalid number {}", s)))
+}
+
+/// Splits a line of the fixed MPS format in its fields, at columns
+/// 2-3, 5-12, 15-22, 25-36, 40-47 and 50-61.
+fn fixed_fields(line: &str) -> Vec<&str> {
+    const FIELDS: [(usize, usize); 6] = [(1, 3), (4, 12), (14, 22), (24, 36), (39, 47), (49, 61)];
+    let mut fields: Vec<&str> = FIELDS
+        .iter()
+        .map(|&(begin, end)| line.get(begin..end.min(line.len())).unwrap_or("").trim())
+        .collect();
+    while fields.last() == Some(&"") {
+        fields.pop();
+    }
+    // The first field is only used in the ROWS and BOUNDS sections.
+    if fields.first() == Some(&"") {
+        fields.remove(0);
+    }
+    fields
+}
@@ -0,0 +1,439 @@
+//! Reader and writer for the OPB format of pseudo-Boolean instances.
+//!
+//! Variables are named `x1`, `x2`, ... and `~x1` is the negation of
+//! `x1`. Constraints use `>=`, `=` and, as an extension, `<=`. Non
+//! linear terms, products of literals, are replaced by auxiliary
+//! variables equal to the conjunction of their literals. Weighted
+//! Boolean optimization (`soft:`) instances are rejected with
+//! [Error::Unsupported].
+//!
+//! # Example
+//!
+//! ```
+//! # use cp_sat::formats::opb;
+//! let text = "\
+//! * #variable= 3 #constraint= 2
+//! min: +1 x1 +2 x2 -1 x3 ;
+//! +1 x1 +1 x2 >= 1 ;
+//! +2 x1 x3 +1 ~x2 = 1 ;
+//! ";
+//! let instance = opb::parse(text).unwrap();
+//! assert_eq!(3, instance.vars.len());
+//! assert_eq!(1, instance.num_products);
+//! // 2 linear constraints, and 3 clauses for the product x1 x3.
+//! assert_eq!(5, instance.model.proto().constraints.len());
+//! ```
+
+use super::Error;
+use crate::builder::{BoolVar, CpModelBuilder, LinearExpr};
+use crate::proto;
+use crate::refs::var_index;
+use proto::constraint_proto::Constraint as CstEnum;
+use std::collections::HashMap;
+
+/// A pseudo-Boolean instance translated to CP SAT.
+#[derive(Debug)]
+pub struct OpbModel {
+    /// The translated model.
+    pub model: CpModelBuilder,
+    /// The variables of the instance, `vars[i]` being `x{i + 1}`.
+    pub vars: Vec<BoolVar>,
+    /// The number of auxiliary variables created for the products of
+    /// literals.
+    pub num_products: usize,
+}
+
+/// Parses an instance in the OPB format.
+///
+/// The `* #variable= n` header is only a hint, ignored if it declares
+/// more variables than the number of bytes of the text. For the same
+/// reason, variables with a larger index are rejected.
+pub fn parse(text: &str) -> Result<OpbModel, Error> {
+    let mut parser = Parser {
+        instance: OpbModel {
+            model: CpModelBuilder::default(),
+            vars: Vec::new(),
+            num_products: 0,
+        },
+        products: HashMap::new(),
+        max_vars: text.len(),
+    };
+    let mut statement = String::new();
+    let mut statement_line = 1;
+    for (line_index, line) in text.lines().enumerate() {
+        let line_number = line_index + 1;
+        if line.starts_with('*') {
+            if let Some(num_vars) = header_num_vars(line).filter(|&n| n <= parser.max_vars) {
+                parser.new_vars(num_vars);
+            }
+            continue;
+        }
+        let mut rest = line;
+        while let Some(end) = rest.find(';') {
+            if statement.trim().is_empty() {
+                statement_line = line_number;
+            }
+            statement.push_str(&rest[..end]);
+            parser.statement(&statement, statement_line)?;
+            statement.clear();
+            rest = &rest[end + 1..];
+        }
+        if statement.trim().is_empty() {
+            statement_line = line_number;
+        }
+        statement.push_str(rest);
+        statement.push(' ');
+    }
+    if !statement.trim().is_empty() {
+        return Err(Error::parse(
+            statement_line,
+            "the last statement is not terminated by ;",
+        ));
+    }
+    Ok(parser.instance)
+}
+
+/// Reads an instance file in the OPB format.
+pub fn read(path: impl AsRef<std::path::Path>) -> Result<OpbModel, Error> {
+    parse(&std::fs::read_to_string(path)?)
+}
+
+/// Returns the number of variables of a `* #variable= n` header.
+fn header_num_vars(line: &str) -> Option<usize> {
+    let mut tokens = line.split_whitespace();
+    tokens.find(|&t| t == "#variable=")?;
+    tokens.next()?.parse().ok()
+}
+
+struct Parser {
+    instance: OpbModel,
+    /// Auxiliary variables of the products, by sorted literals.
+    products: HashMap<Vec<BoolVar>, BoolVar>,
+    /// Bounds the number of variables, to avoid huge allocations.
+    max_vars: usize,
+}
+
+impl Parser {
+    fn new_vars(&mut self, num_vars: usize) {
+        while self.instance.vars.len() < num_vars {
+            let name = format!("x{}", self.instance.vars.len() + 1);
+            let var = self.instance.model.new_bool_var_with_name(name);
+            self.instance.vars.push(var);
+        }
+    }
+
+    fn statement(&mut self, statement: &str, line: usize) -> Result<(), Error> {
+        let statement = statement.trim();
+        if statement.is_empty() {
+            return Ok(());
+        }
+        for (prefix, maximize) in [("min:", false), ("max:", true)] {
+            if let Some(objective) = statement.strip_prefix(prefix) {
+                let expr = self.expr(objective, line)?;
+                if maximize {
+                    self.instance.model.maximize(expr);
+                } else {
+                    self.instance.model.minimize(expr);
+                }
+                return Ok(());
+            }
+        }
+        if statement.starts_with("soft:") {
+            return Err(Error::Unsupported(format!(
+                "line {}: weighted Boolean optimization",
+                line
+            )));
+        }
+
+        let (op_begin, op) = ["<=", ">=", "="]
+            .iter()
+            .filter_map(|&op| statement.find(op).map(|begin| (begin, op)))
+            .min()
+            .ok_or_else(|| Error::parse(line, "expected >=, = or <="))?;
+        let expr = self.expr(&statement[..op_begin], line)?;
+        let rhs = statement[op_begin + op.len()..].trim();
+        let rhs = parse_int(rhs, line)?;
+        let model = &mut self.instance.model;
+        match op {
+            ">=" => model.add_ge(expr, rhs),
+            "<=" => model.add_le(expr, rhs),
+            _ => model.add_eq(expr, rhs),
+        };
+        Ok(())
+    }
+
+    fn expr(&mut self, text: &str, line: usize) -> Result<LinearExpr, Error> {
+        let mut expr = LinearExpr::default();
+        let mut tokens = text.split_whitespace().peekable();
+        while let Some(&token) = tokens.peek() {
+            let coeff = if is_literal(token) {
+                1
+            } else {
+                tokens.next();
+                parse_int(token, line)?
+            };
+            let mut lits = Vec::new();
+            while let Some(&token) = tokens.peek().filter(|t| is_literal(t)) {
+                tokens.next();
+                lits.push(self.literal(token, line)?);
+            }
+            let term = match lits.as_slice() {
+                [] => return Err(Error::parse(line, "constant terms are not supported")),
+                [lit] => *lit,
+                _ => self.product(lits),
+            };
+            expr += (coeff, term);
+        }
+        Ok(expr)
+    }
+
+    fn literal(&mut self, token: &str, line: usize) -> Result<BoolVar, Error> {
+        let (negated, name) = match token.strip_prefix('~') {
+            Some(name) => (true, name),
+            None => (false, token),
+        };
+        let index: usize = name
+            .strip_prefix('x')
+            .and_then(|i| i.parse().ok())
+            .filter(|&i| i > 0)
+            .ok_or_else(|| Error::parse(line, format!("invalid variable {}", name)))?;
+        if index > self.max_vars {
+            return Err(Error::parse(
+                line,
+                format!(
+                    "variable {} is too large for an instance of {} bytes",
+                    name, self.max_vars
+                ),
+            ));
+        }
+        self.new_vars(index);
+        let var = self.instance.vars[index - 1];
+        Ok(if negated { !var } else { var })
+    }
+
+    /// Returns an auxiliary variable equal to the conjunction of the
+    /// literals.
+    fn product(&mut self, mut lits: Vec<BoolVar>) -> BoolVar {
+        lits.sort();
+        lits.dedup();
+        if let Some(&product) = self.products.get(&lits) {
+            return product;
+        }
+        let model = &mut self.instance.model;
+        let product = model.new_bool_var();
+        for &lit in &lits {
+            model.add_or([!product, lit]);
+        }
+        model.add_or(lits.iter().map(|&lit| !lit).chain(Some(product)));
+        self.instance.num_products += 1;
+        self.products.insert(lits, product);
+        product
+    }
+}
+
+fn is_literal(token: &str) -> bool {
+    token.starts_with('x') || token.starts_with("~x")
+}
+
+fn parse_int(token: &str, line: usize) -> Result<i64, Error> {
+    token
+        .parse()
+        .map_err(|_| Error::parse(line, format!("invalid integer {}", token)))
+}
+
+/// Writes a pure Boolean model in the OPB format.
+///
+/// Variable `i` of the model is written `x{i + 1}`. Linear
+/// constraints, clauses, `bool_and`, `at_most_one` and `exactly_one`
+/// constraints are supported, enforcement literals being linearized
+/// with big-M coefficients. The objective is written as the
+/// minimization of its internal linear expression, without its offset
+/// and scaling factor. Models with other constraints or non Boolean
+/// variables are rejected with [Error::Unsupported].
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::builder::CpModelBuilder;
+/// # use cp_sat::formats::opb;
+/// let mut model = CpModelBuilder::default();
+/// let x = model.new_bool_var();
+/// let y = model.new_bool_var();
+/// model.add_le([(2, x), (3, y)], 4);
+/// model.minimize([(1, x), (-1, y)]);
+/// let mut text = Vec::new();
+/// opb::write(&model, &mut text).unwrap();
+/// assert_eq!(
+///     "* #variable= 2 #constraint= 1\nmin: +1 x1 -1 x2 ;\n-2 x1 -3 x2 >= -4 ;\n",
+///     String::from_utf8(text).unwrap()
+/// );
+/// ```
+pub fn write(model: &CpModelBuilder, mut out: impl std::io::Write) -> Result<(), Error> {
+    let proto = model.proto();
+    let mut constraints = Vec::new();
+    for (index, var) in proto.variables.iter().enumerate() {
+        match var.domain.as_slice() {
+            [0, 1] => {}
+            &[value @ (0 | 1), ub] if value == ub => {
+                constraints.push(PbConstraint::linear(vec![(1, index as i32)], value, value))
+            }
+            _ => {
+                return Err(Error::Unsupported(format!(
+                    "variable #{} is not Boolean",
+                    index
+                )))
+            }
+        }
+    }
+    for (index, cst) in proto.constraints.iter().enumerate() {
+        let unsupported = || {
+            Error::Unsupported(format!(
+                "constraint #{} cannot be written as pseudo-Boolean constraints",
+                index
+            ))
+        };
+        let ones = |lits: &[i32]| lits.iter().map(|&l| (1, l)).collect::<Vec<_>>();
+        let mut new_constraints = match &cst.constraint {
+            None => continue,
+            Some(CstEnum::BoolOr(arg)) => vec![PbConstraint::at_least(ones(&arg.literals), 1)],
+            Some(CstEnum::BoolAnd(arg)) => arg
+                .literals
+                .iter()
+                .map(|&l| PbConstraint::at_least(vec![(1, l)], 1))
+                .collect(),
+            Some(CstEnum::AtMostOne(arg)) => {
+                vec![PbConstraint::linear(ones(&arg.literals), i64::MIN, 1)]
+            }
+            Some(CstEnum::ExactlyOne(arg)) => {
+                vec![PbConstraint::linear(ones(&arg.literals), 1, 1)]
+            }
+            Some(CstEnum::Linear(arg)) => match arg.domain.as_slice() {
+                &[lb, ub] => {
+                    // Negative references in linear constraints are
+                    // negated variables, not negated literals.
+                    let terms = arg
+                        .vars
+                        .iter()
+                        .zip(&arg.coeffs)
+                        .map(|(&v, &c)| {
+                            let sign = if v < 0 { -1 } else { 1 };
+                            (sign * c, var_index(v) as i32)
+                        })
+                        .collect();
+                    vec![PbConstraint::linear(terms, lb, ub)]
+                }
+                _ => return Err(unsupported()),
+            },
+            Some(_) => return Err(unsupported()),
+        };
+        for pb in &mut new_constraints {
+            pb.enforcement = cst.enforcement_literal.clone();
+        }
+        constraints.extend(new_constraints);
+    }
+
+    let mut lines = Vec::new();
+    for pb in &constraints {
+        pb.write_lines(&mut lines).ok_or_else(|| {
+            Error::Unsupported("a constraint has coefficients too large for 64 bits".into())
+        })?;
+    }
+    writeln!(
+        out,
+        "* #variable= {} #constraint= {}",
+        proto.variables.len(),
+        lines.len()
+    )?;
+    if let Some(objective) = &proto.objective {
+        write!(out, "min:")?;
+        for (&var, &coeff) in objective.vars.iter().zip(&objective.coeffs) {
+            let coeff = if var < 0 { -coeff } else { coeff };
+            write!(out, " {:+} {}", coeff, literal(var_index(var) as i32))?;
+        }
+        writeln!(out, " ;")?;
+    }
+    for line in lines {
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// `lb <= sum(coeff * lit) <= ub` over literals, enforced by all the
+/// `enforcement` literals.
+struct PbConstraint {
+    terms: Vec<(i64, i32)>,
+    lb: i64,
+    ub: i64,
+    enforcement: Vec<i32>,
+}
+
+impl PbConstraint {
+    fn linear(terms: Vec<(i64, i32)>, lb: i64, ub: i64) -> Self {
+        Self {
+            terms,
+            lb,
+            ub,
+            enforcement: Vec::new(),
+        }
+    }
+
+    fn at_least(terms: Vec<(i64, i32)>, lb: i64) -> Self {
+        Self::linear(terms, lb, i64::MAX)
+    }
+
+    /// Writes `sum >= lb` and `-sum >= -ub` unless they are trivially
+    /// true. Returns `None` on overflow.
+    fn write_lines(&self, lines: &mut Vec<String>) -> Option<()> {
+        let min = self
+            .terms
+            .iter()
+            .try_fold(0i64, |acc, &(c, _)| acc.checked_add(c.min(0)))?;
+        let max = self
+            .terms
+            .iter()
+            .try_fold(0i64, |acc, &(c, _)| acc.checked_add(c.max(0)))?;
+        if self.lb == self.ub && self.enforcement.is_empty() {
+            lines.push(format!(
+                "{} = {} ;",
+                terms_to_string(&self.terms, 1)?,
+                self.lb
+            ));
+            return Some(());
+        }
+        if self.lb > min {
+            lines.push(self.at_least_line(1, self.lb, min)?);
+        }
+        if self.ub < max {
+            lines.push(self.at_least_line(-1, self.ub.checked_neg()?, max.checked_neg()?)?);
+        }
+        Some(())
+    }
+
+    /// Returns `sign * sum + big_m * sum(~enforcement) >= rhs`, where
+    /// `min` is the minimum of `sign * sum`.
+    fn at_least_line(&self, sign: i64, rhs: i64, min: i64) -> Option<String> {
+        let mut line = terms_to_string(&self.terms, sign)?;
+        let big_m = rhs.checked_sub(min)?;
+        for &e in &self.enforcement {
+            line.push_str(&format!(" {:+} {}", big_m, literal(-e - 1)));
+        }
+        Some(format!("{} >= {} ;", line, rhs))
+    }
+}
+
+fn terms_to_string(terms: &[(i64, i32)], sign: i64) -> Option<String> {
+    let mut res = Vec::with_capacity(terms.len());
+    for &(coeff, lit) in terms {
+        res.push(format!("{:+} {}", coeff.checked_mul(sign)?, literal(lit)));
+    }
+    Some(res.join(" "))
+}
+
+/// Formats a literal reference, negative references being negations.
+fn literal(lit: i32) -> String {
+    if lit < 0 {
+        format!("~x{}", var_index(lit) + 1)
+    } else {
+        format!("x{}", lit + 1)
+    }
+}
@@ -9,6 +9,15 @@
 //! to provide your OR-Tools installation directory, you can define
 //! the `ORTOOL_PREFIX` environment variable.
 //!
+//! # Features
+//!
+//! - `serde`: derives `Serialize` and `Deserialize` for the
+//!   [proto] types, the [builder] handles and the diagnostics of this
+//!   crate.
+//! - `cli`: builds the `cp-sat` binary, which solves a model stored
+//!   in a file, in any format supported by [builder::Format] or
+//!   [formats]. Run `cp-sat --help` for its usage.
+//!
 //! # Brief overview
 //!
 //! The [builder::CpModelBuilder] provides an easy interface to
@@ -58,4 +67,22 @@ pub mod proto {
 /// Interface with the CP SAT functions.
 pub mod ffi;
 
+/// Structured statistics on models and solver responses.
+pub mod stats;
+
+/// Pure Rust validation of models.
+pub mod validation;
+
+/// Detection of common modelling mistakes.
+pub mod lint;
+
+/// Export of models to the GraphViz DOT format.
+pub mod dot;
+
+/// Readers and writers for the file formats of other solvers.
+pub mod formats;
+
+mod numeric;
+mod refs;
+
 pub use prost;
@@ -0,0 +1,262 @@
+use crate::refs;
+use crate::validation::Location;
+use crate::{proto, stats::ConstraintKind};
+use prost::Message;
+use proto::constraint_proto::Constraint as CstEnum;
+use std::collections::{hash_map::Entry, HashMap};
+
+/// The severity of a [Lint].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    /// Probably harmless, but worth a look.
+    Info,
+    /// Likely to hurt the performance or to be a modelling mistake.
+    Warning,
+    /// The model will be rejected or is trivially infeasible.
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        f.write_str(match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// The kind of modelling mistake detected by a [Lint].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LintKind {
+    /// A variable has a domain with very large bounds.
+    HugeDomain,
+    /// A linear term has a very large coefficient, typical of big-M
+    /// formulations.
+    BigMCoefficient,
+    /// A variable is used neither in a constraint nor in the
+    /// objective.
+    UnusedVariable,
+    /// A constraint is identical to a previous one, names excepted.
+    DuplicateConstraint,
+    /// A constraint has no content. The default constraints left by
+    /// [CpModelBuilder::remove_constraint][crate::builder::CpModelBuilder::remove_constraint]
+    /// are not reported.
+    EmptyConstraint,
+}
+
+/// A potential modelling mistake found by [lint].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lint {
+    /// The severity of the problem.
+    pub severity: Severity,
+    /// The kind of problem.
+    pub kind: LintKind,
+    /// The element of the model with the problem.
+    pub location: Location,
+    /// A human-readable description of the problem.
+    pub message: String,
+    /// A human-readable suggestion to fix the problem, if any.
+    pub suggestion: Option<String>,
+}
+
+impl std::fmt::Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}: {}: {}", self.severity, self.location, self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " ({})", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Thresholds used by [lint].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LintOptions {
+    /// Variable bounds with an absolute value above this threshold
+    /// are reported as [LintKind::HugeDomain]. Defaults to 10^12.
+    pub max_domain_magnitude: i64,
+    /// Linear coefficients with an absolute value above this
+    /// threshold are reported as [LintKind::BigMCoefficient].
+    /// Defaults to 10^6.
+    pub max_coefficient: i64,
+}
+
+impl Default for LintOptions {
+    fn default() -> Self {
+        Self {
+            max_domain_magnitude: 1_000_000_000_000,
+            max_coefficient: 1_000_000,
+        }
+    }
+}
+
+/// Looks for common modelling mistakes in the given model.
+///
+/// Contrary to [validation::validate][crate::validation::validate],
+/// the reported problems do not make the model invalid, but are
+/// likely to be mistakes or to slow down the solver. The lints are
+/// sorted by decreasing severity.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::builder::CpModelBuilder;
+/// # use cp_sat::lint::{lint, LintKind, LintOptions, Severity};
+/// let mut model = CpModelBuilder::default();
+/// let x = model.new_int_var_with_name([(0, 10)], "x");
+/// let _unused = model.new_bool_var_with_name("unused");
+/// model.add_le(x, 5);
+/// model.add_le(x, 5);
+/// let lints = lint(model.proto(), &LintOptions::default());
+/// assert_eq!(2, lints.len());
+/// assert_eq!(LintKind::DuplicateConstraint, lints[0].kind);
+/// assert_eq!(Severity::Warning, lints[0].severity);
+/// assert_eq!(LintKind::UnusedVariable, lints[1].kind);
+/// assert_eq!("info: variable #1 (unused): variable is not used in any constraint nor in the objective (remove it)", lints[1].to_string());
+/// ```
+pub fn lint(model: &proto::CpModelProto, options: &LintOptions) -> Vec<Lint> {
+    let mut lints = Vec::new();
+    let mut used = vec![false; model.variables.len()];
+    let mut seen = HashMap::new();
+
+    for (index, cst) in model.constraints.iter().enumerate() {
+        if *cst == proto::ConstraintProto::default() {
+            // Placeholder of a removed constraint.
+            continue;
+        }
+        let location = Location::constraint(model, index);
+        for var in refs::vars(cst) {
+            if let Some(used) = used.get_mut(var) {
+                *used = true;
+            }
+        }
+        match &cst.constraint {
+            None => lints.push(Lint {
+                severity: Severity::Warning,
+                kind: LintKind::EmptyConstraint,
+                location: location.clone(),
+                message: "the constraint field is not set".into(),
+                suggestion: Some("remove the constraint".into()),
+            }),
+            Some(CstEnum::BoolOr(arg)) if arg.literals.is_empty() => lints.push(Lint {
+                severity: Severity::Error,
+                kind: LintKind::EmptyConstraint,
+                location: location.clone(),
+                message: "bool_or without literals can never be satisfied".into(),
+                suggestion: None,
+            }),
+            Some(CstEnum::Linear(linear)) => {
+                lint_coeffs(&mut lints, options, &location, &linear.coeffs, || {
+                    if cst.enforcement_literal.is_empty() {
+                        "use an enforcement literal instead of a big-M formulation".into()
+                    } else {
+                        "rescale the constraint".into()
+                    }
+                })
+            }
+            Some(CstEnum::LinMax(arg)) | Some(CstEnum::LinMin(arg)) => {
+                for expr in arg.target.iter().chain(&arg.exprs) {
+                    lint_coeffs(&mut lints, options, &location, &expr.coeffs, || {
+                        "rescale the expression".into()
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        let mut unnamed = cst.clone();
+        unnamed.name.clear();
+        match seen.entry(unnamed.encode_to_vec()) {
+            Entry::Occupied(first) => lints.push(Lint {
+                severity: Severity::Warning,
+                kind: LintKind::DuplicateConstraint,
+                location: location.clone(),
+                message: format!(
+                    "{} constraint is identical to {}",
+                    ConstraintKind::of(cst),
+                    Location::constraint(model, *first.get())
+                ),
+                suggestion: Some(format!("remove {}", location)),
+            }),
+            Entry::Vacant(entry) => {
+                entry.insert(index);
+            }
+        }
+    }
+
+    if let Some(objective) = &model.objective {
+        for &var in &objective.vars {
+            if let Some(used) = used.get_mut(refs::var_index(var)) {
+                *used = true;
+            }
+        }
+        lint_coeffs(
+            &mut lints,
+            options,
+            &Location::Objective,
+            &objective.coeffs,
+            || "rescale the objective".into(),
+        );
+    }
+
+    for (index, var) in model.variables.iter().enumerate() {
+        let location = Location::variable(model, index);
+        if let (Some(&lb), Some(&ub)) = (var.domain.first(), var.domain.last()) {
+            let magnitude = lb.unsigned_abs().max(ub.unsigned_abs());
+            if magnitude > options.max_domain_magnitude.unsigned_abs() {
+                lints.push(Lint {
+                    severity: if lb < i64::MIN + 2 || ub == i64::MAX {
+                        Severity::Error
+                    } else {
+                        Severity::Warning
+                    },
+                    kind: LintKind::HugeDomain,
+                    location: location.clone(),
+                    message: format!("domain [{}, {}] has very large bounds", lb, ub),
+                    suggestion: Some(
+                        "tighten the domain to the values that can actually occur".into(),
+                    ),
+                });
+            }
+        }
+        if !used[index] {
+            lints.push(Lint {
+                severity: Severity::Info,
+                kind: LintKind::UnusedVariable,
+                location,
+                message: "variable is not used in any constraint nor in the objective".into(),
+                suggestion: Some("remove it".into()),
+            });
+        }
+    }
+
+    lints.sort_by_key(|l| std::cmp::Reverse(l.severity));
+    lints
+}
+
+fn lint_coeffs(
+    lints: &mut Vec<Lint>,
+    options: &LintOptions,
+    location: &Location,
+    coeffs: &[i64],
+    suggestion: impl FnOnce() -> String,
+) {
+    if let Some(&coeff) = coeffs
+        .iter()
+        .filter(|c| c.unsigned_abs() > options.max_coefficient.unsigned_abs())
+        .max_by_key(|c| c.unsigned_abs())
+    {
+        lints.push(Lint {
+            severity: Severity::Warning,
+            kind: LintKind::BigMCoefficient,
+            location: location.clone(),
+            message: format!("coefficient {} is very large", coeff),
+            suggestion: Some(suggestion()),
+        });
+    }
+}
@@ -0,0 +1,13 @@
+//! Helpers for the floating point values given to the models.
+
+/// Returns the smallest power of 10, up to `10^max_digits`, such that
+/// all the values multiplied by it are integers.
+pub(crate) fn integer_multiplier(values: &[f64], max_digits: u32) -> Option<f64> {
+    (0..=max_digits).map(|d| 10f64.powi(d as i32)).find(|&m| {
+        values.iter().all(|&v| {
+            let scaled = v * m;
+            scaled.abs() < 2f64.powi(53)
+                && (scaled - scaled.round()).abs() <= 1e-9 * scaled.abs().max(1.)
+        })
+    })
+}
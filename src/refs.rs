@@ -0,0 +1,118 @@
+//! Helpers to visit the variable references of the constraints.
+
+use crate::proto;
+use proto::constraint_proto::Constraint as CstEnum;
+use std::convert::TryFrom;
+
+/// Calls `f` on every variable or literal reference of the
+/// constraint, including its enforcement literals. References can be
+/// negative, see [proto::IntegerVariableProto].
+pub(crate) fn visit_vars_mut(cst: &mut proto::ConstraintProto, f: &mut impl FnMut(&mut i32)) {
+    cst.enforcement_literal.iter_mut().for_each(&mut *f);
+    let cst = match &mut cst.constraint {
+        Some(cst) => cst,
+        None => return,
+    };
+    match cst {
+        CstEnum::BoolOr(arg)
+        | CstEnum::BoolAnd(arg)
+        | CstEnum::AtMostOne(arg)
+        | CstEnum::ExactlyOne(arg)
+        | CstEnum::BoolXor(arg) => arg.literals.iter_mut().for_each(f),
+        CstEnum::IntDiv(arg)
+        | CstEnum::IntMod(arg)
+        | CstEnum::IntMax(arg)
+        | CstEnum::IntMin(arg)
+        | CstEnum::IntProd(arg) => {
+            f(&mut arg.target);
+            arg.vars.iter_mut().for_each(f);
+        }
+        CstEnum::LinMax(arg) | CstEnum::LinMin(arg) => {
+            for expr in arg.target.iter_mut().chain(&mut arg.exprs) {
+                expr.vars.iter_mut().for_each(&mut *f);
+            }
+        }
+        CstEnum::Linear(arg) => arg.vars.iter_mut().for_each(f),
+        CstEnum::AllDiff(arg) => arg.vars.iter_mut().for_each(f),
+        CstEnum::Element(arg) => {
+            f(&mut arg.index);
+            f(&mut arg.target);
+            arg.vars.iter_mut().for_each(f);
+        }
+        CstEnum::Circuit(arg) => arg.literals.iter_mut().for_each(f),
+        CstEnum::Routes(arg) => arg.literals.iter_mut().for_each(f),
+        CstEnum::Table(arg) => arg.vars.iter_mut().for_each(f),
+        CstEnum::Automaton(arg) => arg.vars.iter_mut().for_each(f),
+        CstEnum::Inverse(arg) => {
+            arg.f_direct.iter_mut().for_each(&mut *f);
+            arg.f_inverse.iter_mut().for_each(f);
+        }
+        CstEnum::Reservoir(arg) => {
+            arg.times.iter_mut().for_each(&mut *f);
+            arg.actives.iter_mut().for_each(f);
+        }
+        CstEnum::Interval(arg) => {
+            if arg.start_view.is_none() && arg.end_view.is_none() && arg.size_view.is_none() {
+                f(&mut arg.start);
+                f(&mut arg.end);
+                f(&mut arg.size);
+            }
+            for view in arg
+                .start_view
+                .iter_mut()
+                .chain(&mut arg.end_view)
+                .chain(&mut arg.size_view)
+            {
+                view.vars.iter_mut().for_each(&mut *f);
+            }
+        }
+        CstEnum::NoOverlap(_) | CstEnum::NoOverlap2d(_) => {}
+        CstEnum::Cumulative(arg) => {
+            f(&mut arg.capacity);
+            arg.demands.iter_mut().for_each(f);
+        }
+    }
+}
+
+/// Returns the indices of the variables used by the constraint,
+/// possibly with duplicates.
+pub(crate) fn vars(cst: &proto::ConstraintProto) -> Vec<usize> {
+    let mut res = Vec::new();
+    visit_vars_mut(&mut cst.clone(), &mut |&mut v| res.push(var_index(v)));
+    res
+}
+
+/// Returns the index of the variable of a possibly negated reference.
+pub(crate) fn var_index(var: i32) -> usize {
+    if var < 0 {
+        // -var - 1 without overflow on i32::MIN
+        (!var) as usize
+    } else {
+        var as usize
+    }
+}
+
+/// Calls `f` on every interval reference of the constraint.
+pub(crate) fn visit_intervals_mut(cst: &mut proto::ConstraintProto, f: &mut impl FnMut(&mut i32)) {
+    match &mut cst.constraint {
+        Some(CstEnum::NoOverlap(arg)) => arg.intervals.iter_mut().for_each(f),
+        Some(CstEnum::NoOverlap2d(arg)) => {
+            arg.x_intervals.iter_mut().for_each(&mut *f);
+            arg.y_intervals.iter_mut().for_each(f);
+        }
+        Some(CstEnum::Cumulative(arg)) => arg.intervals.iter_mut().for_each(f),
+        _ => {}
+    }
+}
+
+/// Returns the indices of the interval constraints used by the
+/// constraint. Invalid negative references are ignored.
+pub(crate) fn intervals(cst: &proto::ConstraintProto) -> Vec<usize> {
+    let mut res = Vec::new();
+    visit_intervals_mut(&mut cst.clone(), &mut |&mut i| {
+        if let Ok(i) = usize::try_from(i) {
+            res.push(i);
+        }
+    });
+    res
+}
@@ -0,0 +1,510 @@
+use crate::proto;
+use proto::constraint_proto::Constraint as CstEnum;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The kind of a [proto::ConstraintProto], i.e. which variant of its
+/// `constraint` field is set.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(missing_docs)]
+pub enum ConstraintKind {
+    /// The `constraint` field is not set.
+    Empty,
+    BoolOr,
+    BoolAnd,
+    AtMostOne,
+    ExactlyOne,
+    BoolXor,
+    IntDiv,
+    IntMod,
+    IntMax,
+    LinMax,
+    IntMin,
+    LinMin,
+    IntProd,
+    Linear,
+    AllDiff,
+    Element,
+    Circuit,
+    Routes,
+    Table,
+    Automaton,
+    Inverse,
+    Reservoir,
+    Interval,
+    NoOverlap,
+    NoOverlap2d,
+    Cumulative,
+}
+
+impl ConstraintKind {
+    /// Returns the kind of the given constraint.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cp_sat::builder::CpModelBuilder;
+    /// # use cp_sat::stats::ConstraintKind;
+    /// let mut model = CpModelBuilder::default();
+    /// let x = model.new_bool_var();
+    /// model.add_or([x]);
+    /// assert_eq!(
+    ///     ConstraintKind::BoolOr,
+    ///     ConstraintKind::of(&model.proto().constraints[0])
+    /// );
+    /// ```
+    pub fn of(constraint: &proto::ConstraintProto) -> Self {
+        match &constraint.constraint {
+            None => Self::Empty,
+            Some(CstEnum::BoolOr(_)) => Self::BoolOr,
+            Some(CstEnum::BoolAnd(_)) => Self::BoolAnd,
+            Some(CstEnum::AtMostOne(_)) => Self::AtMostOne,
+            Some(CstEnum::ExactlyOne(_)) => Self::ExactlyOne,
+            Some(CstEnum::BoolXor(_)) => Self::BoolXor,
+            Some(CstEnum::IntDiv(_)) => Self::IntDiv,
+            Some(CstEnum::IntMod(_)) => Self::IntMod,
+            Some(CstEnum::IntMax(_)) => Self::IntMax,
+            Some(CstEnum::LinMax(_)) => Self::LinMax,
+            Some(CstEnum::IntMin(_)) => Self::IntMin,
+            Some(CstEnum::LinMin(_)) => Self::LinMin,
+            Some(CstEnum::IntProd(_)) => Self::IntProd,
+            Some(CstEnum::Linear(_)) => Self::Linear,
+            Some(CstEnum::AllDiff(_)) => Self::AllDiff,
+            Some(CstEnum::Element(_)) => Self::Element,
+            Some(CstEnum::Circuit(_)) => Self::Circuit,
+            Some(CstEnum::Routes(_)) => Self::Routes,
+            Some(CstEnum::Table(_)) => Self::Table,
+            Some(CstEnum::Automaton(_)) => Self::Automaton,
+            Some(CstEnum::Inverse(_)) => Self::Inverse,
+            Some(CstEnum::Reservoir(_)) => Self::Reservoir,
+            Some(CstEnum::Interval(_)) => Self::Interval,
+            Some(CstEnum::NoOverlap(_)) => Self::NoOverlap,
+            Some(CstEnum::NoOverlap2d(_)) => Self::NoOverlap2d,
+            Some(CstEnum::Cumulative(_)) => Self::Cumulative,
+        }
+    }
+
+    /// Returns the name of the corresponding field in the proto, for
+    /// example `"bool_or"` or `"no_overlap_2d"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Empty => "empty",
+            Self::BoolOr => "bool_or",
+            Self::BoolAnd => "bool_and",
+            Self::AtMostOne => "at_most_one",
+            Self::ExactlyOne => "exactly_one",
+            Self::BoolXor => "bool_xor",
+            Self::IntDiv => "int_div",
+            Self::IntMod => "int_mod",
+            Self::IntMax => "int_max",
+            Self::LinMax => "lin_max",
+            Self::IntMin => "int_min",
+            Self::LinMin => "lin_min",
+            Self::IntProd => "int_prod",
+            Self::Linear => "linear",
+            Self::AllDiff => "all_diff",
+            Self::Element => "element",
+            Self::Circuit => "circuit",
+            Self::Routes => "routes",
+            Self::Table => "table",
+            Self::Automaton => "automaton",
+            Self::Inverse => "inverse",
+            Self::Reservoir => "reservoir",
+            Self::Interval => "interval",
+            Self::NoOverlap => "no_overlap",
+            Self::NoOverlap2d => "no_overlap_2d",
+            Self::Cumulative => "cumulative",
+        }
+    }
+}
+
+impl std::fmt::Display for ConstraintKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        f.write_str(self.name())
+    }
+}
+
+/// Structured statistics on a [proto::CpModelProto].
+///
+/// Contrary to [ffi::cp_model_stats][crate::ffi::cp_model_stats],
+/// these statistics are computed in Rust and can be inspected
+/// field by field.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::builder::CpModelBuilder;
+/// # use cp_sat::stats::ConstraintKind;
+/// let mut model = CpModelBuilder::default();
+/// let x = model.new_bool_var();
+/// let y = model.new_int_var([(0, 10)]);
+/// model.add_or([x]);
+/// model.add_le(y, 5);
+/// model.add_hint(y, 3);
+/// model.minimize(y);
+/// let stats = model.model_stats();
+/// assert_eq!(2, stats.num_variables);
+/// assert_eq!(1, stats.num_bool_vars);
+/// assert_eq!(1, stats.num_interval_vars);
+/// assert_eq!(Some(&1), stats.constraints_by_kind.get(&ConstraintKind::Linear));
+/// assert_eq!(Some(1), stats.num_objective_terms);
+/// assert_eq!(0.5, stats.hint_coverage());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModelStats {
+    /// Total number of variables.
+    pub num_variables: usize,
+    /// Number of variables with the domain [0, 1].
+    pub num_bool_vars: usize,
+    /// Number of variables with a domain reduced to a single value.
+    pub num_fixed_vars: usize,
+    /// Number of non boolean, non fixed variables with a domain
+    /// made of a single interval.
+    pub num_interval_vars: usize,
+    /// Number of variables with a domain made of several intervals.
+    pub num_sparse_vars: usize,
+    /// Number of variables with an empty domain.
+    pub num_empty_vars: usize,
+    /// Total number of constraints.
+    pub num_constraints: usize,
+    /// Number of constraints for each kind of constraint present in
+    /// the model.
+    pub constraints_by_kind: BTreeMap<ConstraintKind, usize>,
+    /// Number of constraints with at least one enforcement literal.
+    pub num_enforced_constraints: usize,
+    /// Total number of enforcement literals over all constraints.
+    pub num_enforcement_literals: usize,
+    /// Number of terms in the objective, `None` if there is no
+    /// objective.
+    pub num_objective_terms: Option<usize>,
+    /// Number of distinct variables having a solution hint.
+    pub num_hinted_vars: usize,
+}
+
+impl ModelStats {
+    /// Computes the statistics of the given model.
+    pub fn from_proto(model: &proto::CpModelProto) -> Self {
+        let mut stats = Self {
+            num_variables: model.variables.len(),
+            num_constraints: model.constraints.len(),
+            ..Default::default()
+        };
+        for var in &model.variables {
+            match var.domain.as_slice() {
+                [] => stats.num_empty_vars += 1,
+                [0, 1] => stats.num_bool_vars += 1,
+                [lb, ub] if lb == ub => stats.num_fixed_vars += 1,
+                [_, _] => stats.num_interval_vars += 1,
+                _ => stats.num_sparse_vars += 1,
+            }
+        }
+        for cst in &model.constraints {
+            *stats
+                .constraints_by_kind
+                .entry(ConstraintKind::of(cst))
+                .or_insert(0) += 1;
+            if !cst.enforcement_literal.is_empty() {
+                stats.num_enforced_constraints += 1;
+                stats.num_enforcement_literals += cst.enforcement_literal.len();
+            }
+        }
+        stats.num_objective_terms = model.objective.as_ref().map(|obj| obj.vars.len());
+        if let Some(hint) = &model.solution_hint {
+            stats.num_hinted_vars = hint.vars.iter().collect::<BTreeSet<_>>().len();
+        }
+        stats
+    }
+
+    /// Returns the fraction of the variables having a solution hint,
+    /// 0 if the model has no variable.
+    pub fn hint_coverage(&self) -> f64 {
+        if self.num_variables == 0 {
+            0.
+        } else {
+            self.num_hinted_vars as f64 / self.num_variables as f64
+        }
+    }
+}
+
+impl From<&proto::CpModelProto> for ModelStats {
+    fn from(model: &proto::CpModelProto) -> Self {
+        Self::from_proto(model)
+    }
+}
+
+/// Structured statistics on a [proto::CpSolverResponse].
+///
+/// Contrary to
+/// [ffi::cp_solver_response_stats][crate::ffi::cp_solver_response_stats],
+/// these statistics are directly read from the response, can be
+/// inspected field by field, and can be
+/// [aggregated][SolveStatsAggregate] over several runs. The
+/// [std::fmt::Display] implementation gives a human-readable
+/// summary.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::proto::{CpSolverResponse, CpSolverStatus};
+/// # use cp_sat::stats::SolveStats;
+/// let mut response = CpSolverResponse::default();
+/// response.set_status(CpSolverStatus::Feasible);
+/// response.objective_value = 110.;
+/// response.best_objective_bound = 100.;
+/// response.num_conflicts = 42;
+/// let stats = SolveStats::from_response(&response);
+/// assert_eq!(CpSolverStatus::Feasible, stats.status());
+/// assert_eq!(42, stats.num_conflicts);
+/// assert!((stats.relative_gap() - 10. / 110.).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveStats {
+    /// The [proto::CpSolverStatus] of the solve, as stored in the
+    /// response. Use [SolveStats::status] to get the enum.
+    pub status: i32,
+    /// Objective value of the returned solution.
+    pub objective_value: f64,
+    /// Best proven bound on the objective.
+    pub best_objective_bound: f64,
+    /// Number of boolean variables used by the solver.
+    pub num_booleans: i64,
+    /// Number of conflicts.
+    pub num_conflicts: i64,
+    /// Number of branches.
+    pub num_branches: i64,
+    /// Number of propagations of the boolean variables.
+    pub num_binary_propagations: i64,
+    /// Number of propagations of the integer variables.
+    pub num_integer_propagations: i64,
+    /// Number of restarts.
+    pub num_restarts: i64,
+    /// Number of LP iterations.
+    pub num_lp_iterations: i64,
+    /// Wall time of the solve, in seconds.
+    pub wall_time: f64,
+    /// User time of the solve, in seconds.
+    pub user_time: f64,
+    /// Deterministic time of the solve.
+    pub deterministic_time: f64,
+    /// Integral of the primal gap over the solve.
+    pub primal_integral: f64,
+}
+
+impl SolveStats {
+    /// Extracts the statistics of the given response.
+    pub fn from_response(response: &proto::CpSolverResponse) -> Self {
+        Self {
+            status: response.status,
+            objective_value: response.objective_value,
+            best_objective_bound: response.best_objective_bound,
+            num_booleans: response.num_booleans,
+            num_conflicts: response.num_conflicts,
+            num_branches: response.num_branches,
+            num_binary_propagations: response.num_binary_propagations,
+            num_integer_propagations: response.num_integer_propagations,
+            num_restarts: response.num_restarts,
+            num_lp_iterations: response.num_lp_iterations,
+            wall_time: response.wall_time,
+            user_time: response.user_time,
+            deterministic_time: response.deterministic_time,
+            primal_integral: response.primal_integral,
+        }
+    }
+
+    /// Returns the status of the solve, [proto::CpSolverStatus::Unknown]
+    /// if the stored value is not a valid status.
+    pub fn status(&self) -> proto::CpSolverStatus {
+        proto::CpSolverStatus::from_i32(self.status).unwrap_or(proto::CpSolverStatus::Unknown)
+    }
+
+    /// Returns the total number of propagations, boolean and integer.
+    pub fn num_propagations(&self) -> i64 {
+        self.num_binary_propagations + self.num_integer_propagations
+    }
+
+    /// Returns the absolute gap between the objective value and the
+    /// best objective bound.
+    pub fn absolute_gap(&self) -> f64 {
+        (self.objective_value - self.best_objective_bound).abs()
+    }
+
+    /// Returns the relative gap between the objective value and the
+    /// best objective bound, i.e. the absolute gap divided by
+    /// `max(1, |objective_value|)`.
+    pub fn relative_gap(&self) -> f64 {
+        self.absolute_gap() / self.objective_value.abs().max(1.)
+    }
+}
+
+impl From<&proto::CpSolverResponse> for SolveStats {
+    fn from(response: &proto::CpSolverResponse) -> Self {
+        Self::from_response(response)
+    }
+}
+
+impl std::fmt::Display for SolveStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        writeln!(f, "status: {:?}", self.status())?;
+        writeln!(f, "objective: {}", self.objective_value)?;
+        writeln!(f, "best_bound: {}", self.best_objective_bound)?;
+        writeln!(f, "relative_gap: {}", self.relative_gap())?;
+        writeln!(f, "booleans: {}", self.num_booleans)?;
+        writeln!(f, "conflicts: {}", self.num_conflicts)?;
+        writeln!(f, "branches: {}", self.num_branches)?;
+        writeln!(f, "propagations: {}", self.num_binary_propagations)?;
+        writeln!(f, "integer_propagations: {}", self.num_integer_propagations)?;
+        writeln!(f, "restarts: {}", self.num_restarts)?;
+        writeln!(f, "lp_iterations: {}", self.num_lp_iterations)?;
+        writeln!(f, "walltime: {}", self.wall_time)?;
+        writeln!(f, "usertime: {}", self.user_time)?;
+        writeln!(f, "deterministic_time: {}", self.deterministic_time)?;
+        write!(f, "primal_integral: {}", self.primal_integral)
+    }
+}
+
+/// Statistics aggregated over several solves, for example the runs
+/// of a benchmark.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::proto::{CpSolverResponse, CpSolverStatus};
+/// # use cp_sat::stats::{SolveStats, SolveStatsAggregate};
+/// let runs: Vec<SolveStats> = [1., 3.]
+///     .iter()
+///     .map(|&wall_time| {
+///         let mut response = CpSolverResponse::default();
+///         response.set_status(CpSolverStatus::Optimal);
+///         response.wall_time = wall_time;
+///         response.num_branches = 10;
+///         SolveStats::from_response(&response)
+///     })
+///     .collect();
+/// let aggregate: SolveStatsAggregate = runs.iter().collect();
+/// assert_eq!(2, aggregate.num_runs);
+/// assert_eq!(2, aggregate.num_optimal);
+/// assert_eq!(20, aggregate.total_branches);
+/// assert_eq!(3., aggregate.max_wall_time);
+/// assert_eq!(2., aggregate.mean_wall_time());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveStatsAggregate {
+    /// Number of aggregated solves.
+    pub num_runs: usize,
+    /// Number of solves with the [proto::CpSolverStatus::Optimal] status.
+    pub num_optimal: usize,
+    /// Number of solves with the [proto::CpSolverStatus::Feasible] status.
+    pub num_feasible: usize,
+    /// Number of solves with the [proto::CpSolverStatus::Infeasible] status.
+    pub num_infeasible: usize,
+    /// Number of solves with the [proto::CpSolverStatus::Unknown] or
+    /// [proto::CpSolverStatus::ModelInvalid] status.
+    pub num_unknown: usize,
+    /// Sum of the number of conflicts.
+    pub total_conflicts: i64,
+    /// Sum of the number of branches.
+    pub total_branches: i64,
+    /// Sum of the number of propagations, boolean and integer.
+    pub total_propagations: i64,
+    /// Sum of the number of LP iterations.
+    pub total_lp_iterations: i64,
+    /// Sum of the wall times, in seconds.
+    pub total_wall_time: f64,
+    /// Sum of the user times, in seconds.
+    pub total_user_time: f64,
+    /// Sum of the deterministic times.
+    pub total_deterministic_time: f64,
+    /// Maximum wall time, in seconds.
+    pub max_wall_time: f64,
+    /// Maximum relative gap.
+    pub max_relative_gap: f64,
+}
+
+impl SolveStatsAggregate {
+    /// Adds the statistics of a solve to the aggregate.
+    pub fn add(&mut self, stats: &SolveStats) {
+        use proto::CpSolverStatus::*;
+        self.num_runs += 1;
+        match stats.status() {
+            Optimal => self.num_optimal += 1,
+            Feasible => self.num_feasible += 1,
+            Infeasible => self.num_infeasible += 1,
+            Unknown | ModelInvalid => self.num_unknown += 1,
+        }
+        self.total_conflicts += stats.num_conflicts;
+        self.total_branches += stats.num_branches;
+        self.total_propagations += stats.num_propagations();
+        self.total_lp_iterations += stats.num_lp_iterations;
+        self.total_wall_time += stats.wall_time;
+        self.total_user_time += stats.user_time;
+        self.total_deterministic_time += stats.deterministic_time;
+        self.max_wall_time = self.max_wall_time.max(stats.wall_time);
+        self.max_relative_gap = self.max_relative_gap.max(stats.relative_gap());
+    }
+
+    /// Returns the mean wall time, 0 if there is no run.
+    pub fn mean_wall_time(&self) -> f64 {
+        self.mean(self.total_wall_time)
+    }
+
+    /// Returns the mean deterministic time, 0 if there is no run.
+    pub fn mean_deterministic_time(&self) -> f64 {
+        self.mean(self.total_deterministic_time)
+    }
+
+    fn mean(&self, total: f64) -> f64 {
+        if self.num_runs == 0 {
+            0.
+        } else {
+            total / self.num_runs as f64
+        }
+    }
+}
+
+impl<'a> std::iter::Extend<&'a SolveStats> for SolveStatsAggregate {
+    fn extend<I: IntoIterator<Item = &'a SolveStats>>(&mut self, iter: I) {
+        for stats in iter {
+            self.add(stats);
+        }
+    }
+}
+impl<'a> std::iter::FromIterator<&'a SolveStats> for SolveStatsAggregate {
+    fn from_iter<I: IntoIterator<Item = &'a SolveStats>>(iter: I) -> Self {
+        let mut res = Self::default();
+        res.extend(iter);
+        res
+    }
+}
+
+impl std::fmt::Display for SolveStatsAggregate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        writeln!(f, "runs: {}", self.num_runs)?;
+        writeln!(
+            f,
+            "optimal: {}, feasible: {}, infeasible: {}, unknown: {}",
+            self.num_optimal, self.num_feasible, self.num_infeasible, self.num_unknown
+        )?;
+        writeln!(f, "conflicts: {}", self.total_conflicts)?;
+        writeln!(f, "branches: {}", self.total_branches)?;
+        writeln!(f, "propagations: {}", self.total_propagations)?;
+        writeln!(f, "lp_iterations: {}", self.total_lp_iterations)?;
+        writeln!(
+            f,
+            "walltime: {} (mean: {}, max: {})",
+            self.total_wall_time,
+            self.mean_wall_time(),
+            self.max_wall_time
+        )?;
+        writeln!(f, "usertime: {}", self.total_user_time)?;
+        writeln!(
+            f,
+            "deterministic_time: {} (mean: {})",
+            self.total_deterministic_time,
+            self.mean_deterministic_time()
+        )?;
+        write!(f, "max_relative_gap: {}", self.max_relative_gap)
+    }
+}
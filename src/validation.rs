@@ -0,0 +1,672 @@
+use crate::proto;
+use crate::stats::ConstraintKind;
+use proto::constraint_proto::Constraint as CstEnum;
+use std::convert::TryFrom;
+
+/// The element of a [proto::CpModelProto] a diagnostic refers to.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Location {
+    /// The model as a whole.
+    Model,
+    /// The variable at the given index in `variables`.
+    Variable {
+        /// Index of the variable.
+        index: usize,
+        /// Name of the variable, empty if not set.
+        name: String,
+    },
+    /// The constraint at the given index in `constraints`.
+    Constraint {
+        /// Index of the constraint.
+        index: usize,
+        /// Name of the constraint, empty if not set.
+        name: String,
+    },
+    /// The objective.
+    Objective,
+    /// The solution hint.
+    SolutionHint,
+    /// The search strategy at the given index in `search_strategy`.
+    SearchStrategy(usize),
+    /// The assumptions.
+    Assumptions,
+}
+
+impl Location {
+    pub(crate) fn variable(model: &proto::CpModelProto, index: usize) -> Self {
+        Self::Variable {
+            index,
+            name: model
+                .variables
+                .get(index)
+                .map(|v| v.name.clone())
+                .unwrap_or_default(),
+        }
+    }
+    pub(crate) fn constraint(model: &proto::CpModelProto, index: usize) -> Self {
+        Self::Constraint {
+            index,
+            name: model
+                .constraints
+                .get(index)
+                .map(|c| c.name.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Self::Model => write!(f, "model"),
+            Self::Variable { index, name } if name.is_empty() => write!(f, "variable #{}", index),
+            Self::Variable { index, name } => write!(f, "variable #{} ({})", index, name),
+            Self::Constraint { index, name } if name.is_empty() => {
+                write!(f, "constraint #{}", index)
+            }
+            Self::Constraint { index, name } => write!(f, "constraint #{} ({})", index, name),
+            Self::Objective => write!(f, "objective"),
+            Self::SolutionHint => write!(f, "solution hint"),
+            Self::SearchStrategy(index) => write!(f, "search strategy #{}", index),
+            Self::Assumptions => write!(f, "assumptions"),
+        }
+    }
+}
+
+/// The kind of problem found by [validate].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ValidationErrorKind {
+    /// A variable or literal index does not refer to an existing
+    /// variable.
+    InvalidVariableIndex,
+    /// A literal refers to a variable whose domain is not included in
+    /// [0, 1].
+    NonBooleanLiteral,
+    /// A constraint index does not refer to an existing interval
+    /// constraint.
+    InvalidIntervalIndex,
+    /// A variable has an empty domain.
+    EmptyDomain,
+    /// A domain has an odd number of bounds, or its intervals are not
+    /// sorted, disjoint and non adjacent.
+    InvalidDomain,
+    /// A variable domain is too large for CP SAT to avoid integer
+    /// overflows.
+    DomainTooLarge,
+    /// Some linear terms may overflow a 64-bit integer.
+    PossibleOverflow,
+    /// Some parallel arrays do not have compatible sizes.
+    SizeMismatch,
+    /// Enforcement literals are used on a constraint that does not
+    /// support them.
+    UnsupportedEnforcement,
+    /// A variable has several values in the solution hint, reported
+    /// by [validate_hints].
+    DuplicateHint,
+    /// A hinted value is outside the domain of its variable, reported
+    /// by [validate_hints].
+    HintOutOfDomain,
+}
+
+/// A problem found by [validate].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidationError {
+    /// The element of the model with the problem.
+    pub location: Location,
+    /// The kind of problem.
+    pub kind: ValidationErrorKind,
+    /// A human-readable description of the problem.
+    pub message: String,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Verifies that the given model satisfies the properties described
+/// in the proto comments, and returns all the problems found.
+///
+/// This is a Rust implementation of the main checks of
+/// [ffi::validate_cp_model][crate::ffi::validate_cp_model]. It does
+/// not call CP SAT, does not stop at the first error, and returns
+/// structured diagnostics. An empty result means that no problem was
+/// found.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::builder::CpModelBuilder;
+/// # use cp_sat::validation::{validate, ValidationErrorKind};
+/// let mut model = CpModelBuilder::default();
+/// let x = model.new_int_var_with_name([(0, -1)], "x");
+/// let y = model.new_int_var_with_name([(5, 10), (0, 2)], "y");
+/// model.add_le(x, y);
+/// let errors = validate(model.proto());
+/// assert_eq!(2, errors.len());
+/// assert_eq!(ValidationErrorKind::InvalidDomain, errors[0].kind);
+/// assert_eq!("variable #0 (x): domain [0, -1] is not a sorted list of disjoint intervals", errors[0].to_string());
+/// assert_eq!(ValidationErrorKind::InvalidDomain, errors[1].kind);
+/// ```
+pub fn validate(model: &proto::CpModelProto) -> Vec<ValidationError> {
+    let mut validator = Validator {
+        model,
+        errors: Vec::new(),
+    };
+    validator.run();
+    validator.errors
+}
+
+/// Verifies the solution hint of the model, and returns all the
+/// problems found: variables hinted several times, and hinted values
+/// outside the domain of their variable.
+///
+/// These hints are accepted by CP SAT, but are at best partially
+/// used. The hint references and sizes are checked by [validate],
+/// the hints with invalid references are ignored here.
+///
+/// # Example
+///
+/// ```
+/// # use cp_sat::builder::CpModelBuilder;
+/// # use cp_sat::validation::{validate_hints, ValidationErrorKind};
+/// let mut model = CpModelBuilder::default();
+/// let x = model.new_int_var_with_name([(0, 10)], "x");
+/// model.add_hint(x, 3);
+/// model.add_hint(x, 12);
+/// let errors = validate_hints(model.proto());
+/// assert_eq!(2, errors.len());
+/// assert_eq!(ValidationErrorKind::DuplicateHint, errors[0].kind);
+/// assert_eq!(ValidationErrorKind::HintOutOfDomain, errors[1].kind);
+/// assert_eq!(
+///     "solution hint: value 12 of variable #0 (x) is outside its domain [0, 10]",
+///     errors[1].to_string()
+/// );
+/// ```
+pub fn validate_hints(model: &proto::CpModelProto) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let hint = match &model.solution_hint {
+        Some(hint) => hint,
+        None => return errors,
+    };
+    let mut hinted = vec![false; model.variables.len()];
+    for (&var, &value) in hint.vars.iter().zip(&hint.values) {
+        let (index, value) = if var < 0 {
+            (-(var as i64) - 1, 1 - value)
+        } else {
+            (var as i64, value)
+        };
+        let index = match usize::try_from(index) {
+            Ok(index) if index < hinted.len() => index,
+            _ => continue,
+        };
+        let variable = Location::variable(model, index);
+        if std::mem::replace(&mut hinted[index], true) {
+            errors.push(ValidationError {
+                location: Location::SolutionHint,
+                kind: ValidationErrorKind::DuplicateHint,
+                message: format!("{} is hinted several times", variable),
+            });
+        }
+        let domain = &model.variables[index].domain;
+        if !domain
+            .chunks(2)
+            .any(|c| c.len() == 2 && c[0] <= value && value <= c[1])
+        {
+            errors.push(ValidationError {
+                location: Location::SolutionHint,
+                kind: ValidationErrorKind::HintOutOfDomain,
+                message: format!(
+                    "value {} of {} is outside its domain {}",
+                    value,
+                    variable,
+                    format_domain(domain)
+                ),
+            });
+        }
+    }
+    errors
+}
+
+struct Validator<'a> {
+    model: &'a proto::CpModelProto,
+    errors: Vec<ValidationError>,
+}
+
+impl<'a> Validator<'a> {
+    fn push(&mut self, location: &Location, kind: ValidationErrorKind, message: String) {
+        self.errors.push(ValidationError {
+            location: location.clone(),
+            kind,
+            message,
+        });
+    }
+
+    fn run(&mut self) {
+        let model = self.model;
+        for (index, var) in model.variables.iter().enumerate() {
+            self.check_variable(&Location::variable(model, index), &var.domain);
+        }
+        for (index, cst) in model.constraints.iter().enumerate() {
+            self.check_constraint(&Location::constraint(model, index), cst);
+        }
+        if let Some(objective) = &model.objective {
+            let location = Location::Objective;
+            self.check_linear(&location, &objective.vars, &objective.coeffs);
+            self.check_domain(&location, &objective.domain);
+        }
+        if let Some(hint) = &model.solution_hint {
+            let location = Location::SolutionHint;
+            self.check_vars(&location, &hint.vars);
+            self.check_same_size(
+                &location,
+                "vars",
+                hint.vars.len(),
+                "values",
+                hint.values.len(),
+            );
+        }
+        for (index, strategy) in model.search_strategy.iter().enumerate() {
+            let location = Location::SearchStrategy(index);
+            self.check_vars(&location, &strategy.variables);
+            for transformation in &strategy.transformations {
+                self.check_var(&location, transformation.index);
+            }
+        }
+        self.check_vars(&Location::Assumptions, &model.assumptions);
+    }
+
+    fn check_variable(&mut self, location: &Location, domain: &[i64]) {
+        if domain.is_empty() {
+            self.push(
+                location,
+                ValidationErrorKind::EmptyDomain,
+                "empty domain".into(),
+            );
+            return;
+        }
+        if !self.check_domain(location, domain) {
+            return;
+        }
+        let (lb, ub) = (domain[0], domain[domain.len() - 1]);
+        if lb < i64::MIN + 2 || ub == i64::MAX || ub.checked_sub(lb).is_none() {
+            self.push(
+                location,
+                ValidationErrorKind::DomainTooLarge,
+                format!(
+                    "domain [{}, {}] is too large, bounds must be in [{}, {}] and their difference must fit in 64 bits",
+                    lb,
+                    ub,
+                    i64::MIN + 2,
+                    i64::MAX - 1
+                ),
+            );
+        }
+    }
+
+    /// Returns false if an error has been reported.
+    fn check_domain(&mut self, location: &Location, domain: &[i64]) -> bool {
+        let valid = domain.len() % 2 == 0
+            && domain.chunks(2).all(|c| c[0] <= c[1])
+            && domain
+                .windows(2)
+                .skip(1)
+                .step_by(2)
+                .all(|w| i128::from(w[0]) + 1 < i128::from(w[1]));
+        if !valid {
+            self.push(
+                location,
+                ValidationErrorKind::InvalidDomain,
+                format!(
+                    "domain {} is not a sorted list of disjoint intervals",
+                    format_domain(domain)
+                ),
+            );
+        }
+        valid
+    }
+
+    /// Returns false if an error has been reported.
+    fn check_var(&mut self, location: &Location, var: i32) -> bool {
+        let index = if var < 0 {
+            -(var as i64) - 1
+        } else {
+            var as i64
+        };
+        if index >= self.model.variables.len() as i64 {
+            self.push(
+                location,
+                ValidationErrorKind::InvalidVariableIndex,
+                format!(
+                    "reference {} is out of range, the model has {} variables",
+                    var,
+                    self.model.variables.len()
+                ),
+            );
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Returns false if an error has been reported.
+    fn check_vars(&mut self, location: &Location, vars: &[i32]) -> bool {
+        let mut valid = true;
+        for &var in vars {
+            valid &= self.check_var(location, var);
+        }
+        valid
+    }
+
+    /// Checks the references, and that they refer to boolean
+    /// variables.
+    fn check_literals(&mut self, location: &Location, literals: &[i32]) {
+        for &literal in literals {
+            if !self.check_var(location, literal) {
+                continue;
+            }
+            let index = if literal < 0 { -literal - 1 } else { literal } as usize;
+            let domain = &self.model.variables[index].domain;
+            if domain.first().is_some_and(|&lb| lb < 0) || domain.last().is_some_and(|&ub| ub > 1) {
+                self.push(
+                    location,
+                    ValidationErrorKind::NonBooleanLiteral,
+                    format!(
+                        "literal {} refers to {} with domain {}, which is not included in [0, 1]",
+                        literal,
+                        Location::variable(self.model, index),
+                        format_domain(domain)
+                    ),
+                );
+            }
+        }
+    }
+
+    fn check_interval(&mut self, location: &Location, interval: i32) {
+        let is_interval = usize::try_from(interval)
+            .ok()
+            .and_then(|i| self.model.constraints.get(i))
+            .is_some_and(|c| ConstraintKind::of(c) == ConstraintKind::Interval);
+        if !is_interval {
+            self.push(
+                location,
+                ValidationErrorKind::InvalidIntervalIndex,
+                format!("{} is not the index of an interval constraint", interval),
+            );
+        }
+    }
+
+    fn check_same_size(
+        &mut self,
+        location: &Location,
+        name1: &str,
+        size1: usize,
+        name2: &str,
+        size2: usize,
+    ) -> bool {
+        if size1 != size2 {
+            self.push(
+                location,
+                ValidationErrorKind::SizeMismatch,
+                format!(
+                    "{} and {} have different sizes ({} != {})",
+                    name1, name2, size1, size2
+                ),
+            );
+            false
+        } else {
+            true
+        }
+    }
+
+    fn check_linear(&mut self, location: &Location, vars: &[i32], coeffs: &[i64]) {
+        let valid_vars = self.check_vars(location, vars);
+        let same_size = self.check_same_size(location, "vars", vars.len(), "coeffs", coeffs.len());
+        if !valid_vars || !same_size {
+            return;
+        }
+        let mut sum_min = 0i128;
+        let mut sum_max = 0i128;
+        for (&var, &coeff) in vars.iter().zip(coeffs) {
+            let domain =
+                &self.model.variables[if var < 0 { -var - 1 } else { var } as usize].domain;
+            let (lb, ub) = match (domain.first(), domain.last()) {
+                (Some(&lb), Some(&ub)) => (i128::from(lb), i128::from(ub)),
+                _ => continue,
+            };
+            let (lb, ub) = if var < 0 { (1 - ub, 1 - lb) } else { (lb, ub) };
+            let (a, b) = (lb * i128::from(coeff), ub * i128::from(coeff));
+            sum_min += a.min(b);
+            sum_max += a.max(b);
+        }
+        let range = i128::from(i64::MIN)..=i128::from(i64::MAX);
+        if !range.contains(&sum_min) || !range.contains(&sum_max) {
+            self.push(
+                location,
+                ValidationErrorKind::PossibleOverflow,
+                format!(
+                    "linear expression can take values in [{}, {}], which may overflow a 64-bit integer",
+                    sum_min, sum_max
+                ),
+            );
+        }
+    }
+
+    fn check_linear_expr(&mut self, location: &Location, expr: &proto::LinearExpressionProto) {
+        self.check_linear(location, &expr.vars, &expr.coeffs);
+    }
+
+    fn check_constraint(&mut self, location: &Location, cst: &proto::ConstraintProto) {
+        self.check_literals(location, &cst.enforcement_literal);
+        let kind = ConstraintKind::of(cst);
+        let max_enforcement = match kind {
+            ConstraintKind::BoolOr | ConstraintKind::BoolAnd | ConstraintKind::Linear => None,
+            ConstraintKind::Interval => Some(1),
+            _ => Some(0),
+        };
+        if let Some(max) = max_enforcement {
+            if cst.enforcement_literal.len() > max {
+                self.push(
+                    location,
+                    ValidationErrorKind::UnsupportedEnforcement,
+                    format!(
+                        "{} constraint supports at most {} enforcement literal(s), got {}",
+                        kind,
+                        max,
+                        cst.enforcement_literal.len()
+                    ),
+                );
+            }
+        }
+
+        let cst = match &cst.constraint {
+            Some(cst) => cst,
+            None => return,
+        };
+        match cst {
+            CstEnum::BoolOr(arg)
+            | CstEnum::BoolAnd(arg)
+            | CstEnum::AtMostOne(arg)
+            | CstEnum::ExactlyOne(arg)
+            | CstEnum::BoolXor(arg) => {
+                self.check_literals(location, &arg.literals);
+            }
+            CstEnum::IntDiv(arg)
+            | CstEnum::IntMod(arg)
+            | CstEnum::IntMax(arg)
+            | CstEnum::IntMin(arg)
+            | CstEnum::IntProd(arg) => {
+                self.check_var(location, arg.target);
+                self.check_vars(location, &arg.vars);
+            }
+            CstEnum::LinMax(arg) | CstEnum::LinMin(arg) => {
+                if let Some(target) = &arg.target {
+                    self.check_linear_expr(location, target);
+                }
+                for expr in &arg.exprs {
+                    self.check_linear_expr(location, expr);
+                }
+            }
+            CstEnum::Linear(linear) => {
+                self.check_linear(location, &linear.vars, &linear.coeffs);
+                self.check_domain(location, &linear.domain);
+            }
+            CstEnum::AllDiff(arg) => {
+                self.check_vars(location, &arg.vars);
+            }
+            CstEnum::Element(arg) => {
+                self.check_var(location, arg.index);
+                self.check_var(location, arg.target);
+                self.check_vars(location, &arg.vars);
+            }
+            CstEnum::Circuit(arg) => {
+                self.check_same_size(location, "tails", arg.tails.len(), "heads", arg.heads.len());
+                self.check_same_size(
+                    location,
+                    "tails",
+                    arg.tails.len(),
+                    "literals",
+                    arg.literals.len(),
+                );
+                self.check_literals(location, &arg.literals);
+            }
+            CstEnum::Routes(arg) => {
+                self.check_same_size(location, "tails", arg.tails.len(), "heads", arg.heads.len());
+                self.check_same_size(
+                    location,
+                    "tails",
+                    arg.tails.len(),
+                    "literals",
+                    arg.literals.len(),
+                );
+                self.check_literals(location, &arg.literals);
+            }
+            CstEnum::Table(arg) => {
+                self.check_vars(location, &arg.vars);
+                if arg.vars.is_empty() && !arg.values.is_empty()
+                    || !arg.vars.is_empty() && arg.values.len() % arg.vars.len() != 0
+                {
+                    self.push(
+                        location,
+                        ValidationErrorKind::SizeMismatch,
+                        format!(
+                            "the number of values ({}) is not a multiple of the number of vars ({})",
+                            arg.values.len(),
+                            arg.vars.len()
+                        ),
+                    );
+                }
+            }
+            CstEnum::Automaton(arg) => {
+                self.check_vars(location, &arg.vars);
+                self.check_same_size(
+                    location,
+                    "transition_tail",
+                    arg.transition_tail.len(),
+                    "transition_head",
+                    arg.transition_head.len(),
+                );
+                self.check_same_size(
+                    location,
+                    "transition_tail",
+                    arg.transition_tail.len(),
+                    "transition_label",
+                    arg.transition_label.len(),
+                );
+            }
+            CstEnum::Inverse(arg) => {
+                self.check_vars(location, &arg.f_direct);
+                self.check_vars(location, &arg.f_inverse);
+                self.check_same_size(
+                    location,
+                    "f_direct",
+                    arg.f_direct.len(),
+                    "f_inverse",
+                    arg.f_inverse.len(),
+                );
+            }
+            CstEnum::Reservoir(arg) => {
+                self.check_vars(location, &arg.times);
+                self.check_literals(location, &arg.actives);
+                self.check_same_size(
+                    location,
+                    "times",
+                    arg.times.len(),
+                    "demands",
+                    arg.demands.len(),
+                );
+                if !arg.actives.is_empty() {
+                    self.check_same_size(
+                        location,
+                        "times",
+                        arg.times.len(),
+                        "actives",
+                        arg.actives.len(),
+                    );
+                }
+            }
+            CstEnum::Interval(arg) => {
+                if arg.start_view.is_none() && arg.end_view.is_none() && arg.size_view.is_none() {
+                    self.check_vars(location, &[arg.start, arg.end, arg.size]);
+                }
+                for view in arg
+                    .start_view
+                    .iter()
+                    .chain(&arg.end_view)
+                    .chain(&arg.size_view)
+                {
+                    self.check_linear_expr(location, view);
+                }
+            }
+            CstEnum::NoOverlap(arg) => {
+                for &interval in &arg.intervals {
+                    self.check_interval(location, interval);
+                }
+            }
+            CstEnum::NoOverlap2d(arg) => {
+                self.check_same_size(
+                    location,
+                    "x_intervals",
+                    arg.x_intervals.len(),
+                    "y_intervals",
+                    arg.y_intervals.len(),
+                );
+                for &interval in arg.x_intervals.iter().chain(&arg.y_intervals) {
+                    self.check_interval(location, interval);
+                }
+            }
+            CstEnum::Cumulative(arg) => {
+                self.check_var(location, arg.capacity);
+                self.check_vars(location, &arg.demands);
+                self.check_same_size(
+                    location,
+                    "intervals",
+                    arg.intervals.len(),
+                    "demands",
+                    arg.demands.len(),
+                );
+                for &interval in &arg.intervals {
+                    self.check_interval(location, interval);
+                }
+            }
+        }
+    }
+}
+
+fn format_domain(domain: &[i64]) -> String {
+    let intervals: Vec<String> = domain
+        .chunks(2)
+        .map(|c| match c {
+            [lb, ub] => format!("[{}, {}]", lb, ub),
+            [v] => format!("[{}, ?]", v),
+            _ => unreachable!(),
+        })
+        .collect();
+    intervals.join("∪")
+}
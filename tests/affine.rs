@@ -0,0 +1,87 @@
+use cp_sat::builder::{AffineVar, CpModelBuilder};
+use cp_sat::proto::constraint_proto::Constraint;
+use cp_sat::proto::LinearExpressionProto;
+
+#[test]
+fn affine_normalization() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 10)]);
+    let b = model.new_bool_var();
+
+    let y = -(AffineVar::from(x) * 3 + 2) - 1;
+    assert_eq!((y.coeff(), y.offset()), (-3, -3));
+
+    // 2 * !b + 5 == -2 * b + 7
+    let nb = AffineVar::new(!b, 2, 5);
+    assert_eq!(nb.var(), b.into());
+    assert_eq!((nb.coeff(), nb.offset()), (-2, 7));
+    assert_eq!(
+        LinearExpressionProto::from(nb),
+        LinearExpressionProto {
+            vars: vec![1],
+            coeffs: vec![-2],
+            offset: 7,
+        }
+    );
+}
+
+#[test]
+fn interval_views() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 10)]);
+    let y = model.new_int_var([(0, 10)]);
+    let size = model.new_constant(4);
+    let i = model.add_interval(x, size, AffineVar::from(x) + 4);
+    let j = model.add_interval(y, size, AffineVar::from(y) + 4);
+    model.add_no_overlap([i, j]);
+
+    // no variable added besides the constant, each interval comes
+    // with two linear constraints
+    assert_eq!(model.proto().variables.len(), 3);
+    match &model.proto().constraints[0].constraint {
+        Some(Constraint::Interval(interval)) => {
+            let end = interval.end_view.as_ref().unwrap();
+            assert_eq!(
+                (&end.vars[..], &end.coeffs[..], end.offset),
+                (&[0][..], &[1][..], 4)
+            );
+            assert_eq!(interval.size_view.as_ref().unwrap().vars, [2]);
+        }
+        c => panic!("unexpected constraint {:?}", c),
+    }
+    match &model.proto().constraints.last().unwrap().constraint {
+        Some(Constraint::NoOverlap(no_overlap)) => {
+            assert_eq!(no_overlap.intervals, [0, 3]);
+        }
+        c => panic!("unexpected constraint {:?}", c),
+    }
+}
+
+#[test]
+fn cumulative_demands() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 10)]);
+    let size = model.new_constant(2);
+    let interval = model.add_interval(x, size, AffineVar::from(x) + 2);
+    // demand 3x + 1, through an auxiliary variable
+    let demand = model.new_int_var([(0, 31)]);
+    model.add_eq(demand, AffineVar::new(x, 3, 1));
+    let capacity = model.new_constant(10);
+    model.add_cumulative([interval], [demand], capacity);
+    match &model.proto().constraints.last().unwrap().constraint {
+        Some(Constraint::Cumulative(cumulative)) => {
+            assert_eq!(cumulative.intervals, [0]);
+            assert_eq!(cumulative.demands, [2]);
+            assert_eq!(cumulative.capacity, 3);
+        }
+        c => panic!("unexpected constraint {:?}", c),
+    }
+}
+
+#[test]
+#[should_panic(expected = "overflow in the coefficient or offset of an AffineVar")]
+fn affine_overflow() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 10)]);
+    let _ = AffineVar::from(x) + i64::MAX + 1;
+}
@@ -0,0 +1,54 @@
+use cp_sat::builder::{CpModelBuilder, IntVar};
+
+#[test]
+fn three_dimensional_array() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var_array(&[2, 3, 4], [(0, 1), (5, 5)], "x");
+    assert_eq!(x.len(), 24);
+    assert_eq!(model.proto().variables[23].name, "x[1][2][3]");
+    assert_eq!(model.proto().variables[23].domain, [0, 1, 5, 5]);
+    let index = |v: IntVar| x.iter().position(|w| w == v).unwrap();
+
+    let row: Vec<_> = x.row(1).map(index).collect();
+    assert_eq!(row, (12..24).collect::<Vec<_>>());
+    let column: Vec<_> = x.column(2).map(index).collect();
+    assert_eq!(column, [8, 9, 10, 11, 20, 21, 22, 23]);
+    let slice: Vec<_> = x.slice(&[None, Some(1), Some(3)]).map(index).collect();
+    assert_eq!(slice, [7, 19]);
+    let lines: Vec<Vec<_>> = x
+        .lines(1)
+        .into_iter()
+        .map(|line| line.into_iter().map(index).collect())
+        .collect();
+    assert_eq!(lines.len(), 8);
+    assert_eq!(lines[0], [0, 4, 8]);
+    assert_eq!(lines[5], [13, 17, 21]);
+}
+
+#[test]
+fn unnamed_and_empty_arrays() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_bool_var_array(&[2, 0], "");
+    assert!(x.is_empty());
+    assert_eq!(x.lines(0).len(), 0);
+    let y = model.new_bool_var_array(&[3], "");
+    assert_eq!(y.len(), 3);
+    assert!(model.proto().variables.iter().all(|v| v.name.is_empty()));
+    assert_eq!((&y).into_iter().count(), 3);
+}
+
+#[test]
+#[should_panic(expected = "the array must have at least two dimensions")]
+fn column_of_1d_array() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_bool_var_array(&[3], "x");
+    x.column(0).count();
+}
+
+#[test]
+#[should_panic(expected = "the array must have at least one dimension")]
+fn row_of_0d_array() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_bool_var_array(&[], "x");
+    x.row(0).count();
+}
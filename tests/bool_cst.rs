@@ -23,3 +23,45 @@ fn not_feasible() {
     assert!(!y.solution_value(&response));
     assert!((!y).solution_value(&response));
 }
+
+#[test]
+fn bool_expr_truth_table() {
+    use cp_sat::builder::BoolExpr;
+    for inputs in 0..8 {
+        let mut model = CpModelBuilder::default();
+        let vars: Vec<_> = (0..3).map(|_| model.new_bool_var()).collect();
+        let (x, y, z) = (vars[0], vars[1], vars[2]);
+        for (i, &var) in vars.iter().enumerate() {
+            model.add_and([if inputs >> i & 1 == 1 { var } else { !var }]);
+        }
+        // (x and y) or !(y => z), with a shared subexpression
+        let and = BoolExpr::and([x, y]);
+        let expr = and.clone() | !BoolExpr::implies(y, z) | and;
+        let b = model.encode_bool_expr(&expr);
+        assert_eq!(model.proto().variables.len(), 6);
+
+        let response = model.solve();
+        assert_eq!(response.status(), CpSolverStatus::Optimal);
+        let (x, y, z) = (inputs & 1 == 1, inputs & 2 == 2, inputs & 4 == 4);
+        assert_eq!(b.solution_value(&response), y && (x || !z));
+    }
+}
+
+#[test]
+fn bool_and_or_eq() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_bool_var();
+    let y = model.new_bool_var();
+    let and = model.new_bool_var();
+    let or = model.new_bool_var();
+    model.add_bool_and_eq(and, [x, !y]);
+    model.add_bool_or_eq(or, [!x, y]);
+    model.add_equivalence(and, !or);
+    model.add_implication(or, y);
+    model.add_and([!y]);
+    let response = model.solve();
+    assert_eq!(response.status(), CpSolverStatus::Optimal);
+    assert!(x.solution_value(&response));
+    assert!(and.solution_value(&response));
+    assert!(!or.solution_value(&response));
+}
@@ -0,0 +1,120 @@
+use cp_sat::builder::{AffineVar, CpModelBuilder, IntVar};
+use cp_sat::proto::{self, constraint_proto::Constraint};
+
+/// A machine with two tasks that cannot overlap, and a boolean.
+fn machine() -> CpModelBuilder {
+    let interval = |task: i32| proto::ConstraintProto {
+        constraint: Some(Constraint::Interval(proto::IntervalConstraintProto {
+            start: 3 * task,
+            size: 3 * task + 1,
+            end: 3 * task + 2,
+            ..Default::default()
+        })),
+        ..Default::default()
+    };
+    let mut model = CpModelBuilder::from(proto::CpModelProto {
+        variables: (0..6)
+            .map(|_| proto::IntegerVariableProto {
+                name: String::new(),
+                domain: vec![0, 10],
+            })
+            .collect(),
+        constraints: vec![
+            interval(0),
+            interval(1),
+            proto::ConstraintProto {
+                constraint: Some(Constraint::NoOverlap(proto::NoOverlapConstraintProto {
+                    intervals: vec![0, 1],
+                })),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    });
+    let b = model.new_bool_var_with_name("b");
+    model.add_or([!b]);
+    model.add_hint(b, 0);
+    model
+}
+
+#[test]
+fn merge_remaps_references() {
+    let mut plant = machine();
+    let other = machine();
+    let b = other.bool_var_by_name("b").unwrap();
+    let map = plant.merge(&other);
+    assert!(plant.validate().is_empty());
+    assert_ne!(map.bool_var(b), b);
+    assert_eq!(plant.var_name(map.bool_var(b)), "b");
+    assert_eq!(IntVar::from(map.bool_var(b)), map.var(b.into()));
+
+    let proto = plant.proto();
+    assert_eq!(proto.variables.len(), 14);
+    assert_eq!(proto.constraints.len(), 8);
+    assert_eq!(proto.constraints[..4], machine().proto().constraints[..]);
+    match &proto.constraints[5].constraint {
+        Some(Constraint::Interval(interval)) => {
+            assert_eq!((interval.start, interval.size, interval.end), (10, 11, 12))
+        }
+        c => panic!("unexpected constraint {:?}", c),
+    }
+    match &proto.constraints[6].constraint {
+        Some(Constraint::NoOverlap(no_overlap)) => assert_eq!(no_overlap.intervals, [4, 5]),
+        c => panic!("unexpected constraint {:?}", c),
+    }
+    match &proto.constraints[7].constraint {
+        Some(Constraint::BoolOr(or)) => assert_eq!(or.literals, [-14]),
+        c => panic!("unexpected constraint {:?}", c),
+    }
+    let hint = proto.solution_hint.as_ref().unwrap();
+    assert_eq!(hint.vars, [6, 13]);
+    assert_eq!(hint.values, [0, 0]);
+}
+
+#[test]
+fn extract_keeps_intervals() {
+    let mut plant = machine();
+    plant.merge(&machine());
+    let constraints: Vec<_> = plant.proto().constraints.clone();
+    // only the no overlap of the second machine
+    let no_overlap = plant.constraints().nth(6).unwrap();
+    let sub_model = plant.extract([no_overlap]);
+    assert!(sub_model.validate().is_empty());
+    let proto = sub_model.proto();
+    assert_eq!(proto.variables, plant.proto().variables);
+    assert_eq!(proto.constraints[..2], constraints[4..6]);
+    match &proto.constraints[2].constraint {
+        Some(Constraint::NoOverlap(no_overlap)) => assert_eq!(no_overlap.intervals, [0, 1]),
+        c => panic!("unexpected constraint {:?}", c),
+    }
+}
+
+#[test]
+fn extract_keeps_interval_linears() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 10)]);
+    let size = model.new_int_var([(-2, 4)]);
+    model.add_le(x, 5);
+    let interval = model.add_interval(x, size, AffineVar::from(x) + 3);
+    let no_overlap = model.add_no_overlap([interval]);
+
+    let mut plant = CpModelBuilder::default();
+    let y = plant.new_int_var([(0, 10)]);
+    plant.add_le(y, 7);
+    let map = plant.merge(&model);
+
+    // the interval, its two linear constraints and the no overlap
+    let sub_model = plant.extract([map.constraint(no_overlap)]);
+    let constraints = &sub_model.proto().constraints;
+    assert_eq!(constraints.len(), 4);
+    assert_eq!(constraints[..3], plant.proto().constraints[2..5]);
+    match &constraints[3].constraint {
+        Some(Constraint::NoOverlap(no_overlap)) => assert_eq!(no_overlap.intervals, [0]),
+        c => panic!("unexpected constraint {:?}", c),
+    }
+
+    // the linear constraints are still known after an extraction
+    let no_overlap = sub_model.constraints().last().unwrap();
+    let sub_model = sub_model.extract([no_overlap]);
+    assert_eq!(sub_model.proto().constraints, *constraints);
+}
@@ -0,0 +1,140 @@
+use cp_sat::builder::CpModelBuilder;
+use cp_sat::lint::{lint, LintOptions};
+use cp_sat::proto::constraint_proto::Constraint;
+
+#[test]
+fn domain_edits() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 3), (6, 9), (12, 20)]);
+    let b = model.new_bool_var();
+    model.tighten_var_domain(x, [(2, 7), (9, 15)]);
+    assert_eq!(
+        model.proto().variables[0].domain,
+        [2, 3, 6, 7, 9, 9, 12, 15]
+    );
+    model.tighten_var_domain(x, [(4, 5)]);
+    assert!(model.proto().variables[0].domain.is_empty());
+
+    // fixing !b to true fixes b to false
+    model.set_var_domain(!b, [(1, 1)]);
+    assert_eq!(model.proto().variables[1].domain, [0, 0]);
+    model.tighten_var_domain(!b, [(0, 0)]);
+    assert!(model.proto().variables[1].domain.is_empty());
+}
+
+#[test]
+fn constraint_edits() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_bool_var();
+    let y = model.new_bool_var();
+    let or = model.add_or([x, y]);
+    let and = model.add_and([x, y]);
+    let exactly_one = model.add_exactly_one([x, y]);
+
+    assert!(model.disable_constraint(or));
+    assert!(model.disable_constraint(and));
+    assert!(model.disable_constraint(or));
+    assert!(!model.disable_constraint(exactly_one));
+    // a single false literal is shared by the disabled constraints
+    assert_eq!(model.proto().variables.len(), 3);
+    assert_eq!(model.proto().constraints[0].enforcement_literal, [2]);
+    assert_eq!(model.proto().constraints[1].enforcement_literal, [2]);
+    assert!(model.validate().is_empty());
+
+    model.enable_constraint(and);
+    assert!(model.is_constraint_disabled(or));
+    assert!(!model.is_constraint_disabled(and));
+
+    model.remove_constraint(exactly_one);
+    assert_eq!(model.proto().constraints.len(), 3);
+    assert!(model.proto().constraints[2].constraint.is_none());
+    assert!(matches!(
+        model.proto().constraints[1].constraint,
+        Some(Constraint::BoolAnd(_))
+    ));
+
+    // the placeholders of removed constraints are not linted
+    model.remove_constraint(and);
+    assert!(lint(model.proto(), &LintOptions::default()).is_empty());
+}
+
+#[test]
+fn cached_constants() {
+    let mut model = CpModelBuilder::default();
+    let zero = model.new_constant(0);
+    let f = model.new_false();
+    assert_eq!(cp_sat::builder::IntVar::from(f), zero);
+    assert!(model.is_fixed(!f));
+    assert_eq!(model.var_bounds(!f), Some((1, 1)));
+
+    // a constant that is named or modified is not reused
+    model.set_var_name(zero, "zero");
+    let other = model.new_constant(0);
+    assert_ne!(other, zero);
+    model.set_var_domain(other, [(0, 1)]);
+    assert!(!model.is_fixed(other));
+    assert_ne!(model.new_false(), f);
+    assert_eq!(model.proto().variables.len(), 3);
+}
+
+#[test]
+fn disabling_literal_is_private() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 10)]);
+    let le = model.add_le(x, 5);
+    model.disable_constraint(le);
+    let zero = model.new_constant(0);
+    // x, the disabling literal and zero
+    assert_eq!(model.proto().variables.len(), 3);
+    assert_eq!(model.proto().constraints[0].enforcement_literal, [1]);
+
+    // relaxing the constants does not enable the constraint
+    model.set_var_domain(zero, [(0, 10)]);
+    let f = model.new_false();
+    model.set_var_domain(f, [(0, 1)]);
+    assert!(model.is_constraint_disabled(le));
+    assert!(model.validate().is_empty());
+}
+
+#[test]
+fn user_false_literals_are_kept() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_bool_var();
+    let t = model.new_false();
+    let [fwd, _] = model.add_bool_and_eq(t, [x]);
+    assert!(!model.is_constraint_disabled(fwd));
+    model.enable_constraint(fwd);
+    assert_eq!(model.proto().constraints[0].enforcement_literal, [1]);
+
+    model.disable_constraint(fwd);
+    assert!(model.is_constraint_disabled(fwd));
+    model.enable_constraint(fwd);
+    assert!(!model.is_constraint_disabled(fwd));
+    assert_eq!(model.proto().constraints[0].enforcement_literal, [1]);
+}
+
+#[test]
+fn disabled_constraints_are_merged_and_extracted() {
+    let mut other = CpModelBuilder::default();
+    let x = other.new_bool_var();
+    let or = other.add_or([x]);
+    other.disable_constraint(or);
+
+    let mut model = CpModelBuilder::default();
+    let y = model.new_bool_var();
+    let and = model.add_and([y]);
+    model.disable_constraint(and);
+    let map = model.merge(&other);
+    let or = map.constraint(or);
+    assert!(model.is_constraint_disabled(or));
+    // the merged constraint uses the disabling literal of the model
+    assert_eq!(
+        model.proto().constraints[1].enforcement_literal,
+        model.proto().constraints[0].enforcement_literal
+    );
+    model.enable_constraint(or);
+    assert!(!model.is_constraint_disabled(or));
+
+    let sub_model = model.extract([and]);
+    assert!(sub_model.is_constraint_disabled(and));
+}
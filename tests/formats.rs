@@ -0,0 +1,374 @@
+use cp_sat::builder::CpModelBuilder;
+use cp_sat::formats::{dimacs, fzn, lp, mip::MipOptions, mps, opb, Error};
+use cp_sat::proto::{
+    constraint_proto::Constraint, ConstraintProto, CpSolverResponse, CpSolverStatus,
+    IntegerVariableProto, LinearConstraintProto,
+};
+
+fn linear(mip: &cp_sat::formats::mip::MipModel, index: usize) -> &LinearConstraintProto {
+    match &mip.model.proto().constraints[index].constraint {
+        Some(Constraint::Linear(linear)) => linear,
+        cst => panic!("expected a linear constraint, got {:?}", cst),
+    }
+}
+
+#[test]
+fn mps_fixed_format_with_ranges() {
+    let text = "\
+NAME          RANGED
+ROWS
+ N  COST
+ E  ROW A
+ G  ROW B
+COLUMNS
+    MARKER                 'MARKER'                 'INTORG'
+    X         COST         1.             ROW A        1.
+    X         ROW B        2.
+    Y         COST         -1.            ROW A        1.
+    MARKER                 'MARKER'                 'INTEND'
+RHS
+    RHS       COST         -5.            ROW A        4.
+    RHS       ROW B        1.
+RANGES
+    RNG       ROW A        -2.            ROW B        3.
+BOUNDS
+ UP BND       X            10.
+ MI BND       Y
+ UP BND       Y            10.
+ENDATA
+";
+    let mip = mps::parse_fixed(text, &MipOptions::default()).unwrap();
+    assert_eq!("RANGED", mip.model.proto().name);
+    assert_eq!("ROW A", mip.model.proto().constraints[0].name);
+    assert_eq!(vec![2, 4], linear(&mip, 0).domain);
+    assert_eq!(vec![1, 4], linear(&mip, 1).domain);
+    assert_eq!(vec![-10_000_000, 10], mip.model.proto().variables[1].domain);
+    let objective = mip.model.proto().objective.as_ref().unwrap();
+    assert_eq!(vec![1, -1], objective.coeffs);
+    assert_eq!(5., objective.offset);
+
+    // text past the last field
+    let text = format!("OBJSENSE\n{:62}MAX\nENDATA\n", "");
+    assert!(matches!(
+        mps::parse_fixed(&text, &MipOptions::default()),
+        Err(Error::Parse { line: 2, .. })
+    ));
+}
+
+#[test]
+fn continuous_columns() {
+    let text = "\
+ROWS
+ N obj
+ L c
+COLUMNS
+ x obj 1 c 0.5
+RHS
+ rhs c 2.5
+ENDATA
+";
+    assert!(matches!(
+        mps::parse(text, &MipOptions::default()),
+        Err(Error::Unsupported(_))
+    ));
+
+    let options = MipOptions {
+        continuous_scale: Some(10),
+        ..Default::default()
+    };
+    let mip = mps::parse(text, &options).unwrap();
+    let x = mip.column("x").unwrap();
+    assert_eq!(10, x.scale);
+    // 0.5 x <= 2.5 with x = v / 10 is scaled to 5 v <= 250.
+    assert_eq!(vec![5], linear(&mip, 0).coeffs);
+    assert_eq!(vec![i64::MIN, 250], linear(&mip, 0).domain);
+    let objective = mip.model.proto().objective.as_ref().unwrap();
+    assert_eq!(vec![1], objective.coeffs);
+    assert_eq!(0.1, objective.scaling_factor);
+
+    let options = MipOptions {
+        continuous_scale: Some(0),
+        ..Default::default()
+    };
+    assert!(matches!(
+        mps::parse(text, &options),
+        Err(Error::Unsupported(_))
+    ));
+}
+
+#[test]
+fn lp_bounds_and_binaries() {
+    let text = r"
+Minimize
+ obj: - x + 2.5 y - 3 b + 1
+Subject To
+ c1: x + y + b >= 2 \ comment
+ x - 2 y
+   <= 3
+Bounds
+ -inf <= x <= 5
+ y free
+ -2 <= y
+Generals
+ x y
+Binaries
+ b
+End
+";
+    let mip = lp::parse(text, &MipOptions::default()).unwrap();
+    let proto = mip.model.proto();
+    assert_eq!(vec![-10_000_000, 5], proto.variables[0].domain);
+    assert_eq!(vec![-2, 10_000_000], proto.variables[1].domain);
+    assert_eq!(vec![0, 1], proto.variables[2].domain);
+    assert_eq!("R2", proto.constraints[1].name);
+    assert_eq!(vec![1, -2], linear(&mip, 1).coeffs);
+    let objective = proto.objective.as_ref().unwrap();
+    assert_eq!(vec![-10, 25, -30], objective.coeffs);
+    assert_eq!(10., objective.offset);
+    assert_eq!(0.1, objective.scaling_factor);
+}
+
+#[test]
+fn lp_errors() {
+    let options = MipOptions::default();
+    assert!(matches!(
+        lp::parse("Maximize\n x\nSubject To\n c: x <=\nEnd", &options),
+        Err(Error::Parse { line: 4, .. })
+    ));
+    assert!(matches!(
+        lp::parse(
+            "Maximize\n x\nSubject To\n c: [ x ^ 2 ] <= 1\nEnd",
+            &options
+        ),
+        Err(Error::Unsupported(_))
+    ));
+    // multi-byte character right after a number
+    assert!(matches!(
+        lp::parse("Maximize\n 3é\nEnd", &options),
+        Err(Error::Parse { line: 2, .. })
+    ));
+}
+
+#[test]
+fn dimacs_roundtrip() {
+    let cnf = "c a comment\np cnf 3 2\n1 -3\n0 2 3 -1 0\n";
+    let instance = dimacs::parse(cnf).unwrap();
+    assert_eq!(3, instance.vars.len());
+    assert!(instance.soft_clauses.is_empty());
+    assert!(instance.model.proto().objective.is_none());
+    let mut written = Vec::new();
+    dimacs::write_cnf(&instance.model, &mut written).unwrap();
+    assert_eq!(
+        "p cnf 3 2\n1 -3 0\n2 3 -1 0\n",
+        String::from_utf8(written).unwrap()
+    );
+}
+
+#[test]
+fn dimacs_2022_wcnf() {
+    let wcnf = "h 1 2 0\n5 -1 -2 0\n3 -1 0\n";
+    let instance = dimacs::parse(wcnf).unwrap();
+    assert_eq!(2, instance.soft_clauses.len());
+    assert_eq!(2, instance.model.proto().constraints.len());
+    assert_eq!(5, instance.soft_clauses[0].weight);
+    assert!(matches!(
+        dimacs::write_cnf(&instance.model, Vec::new()),
+        Err(Error::Unsupported(_))
+    ));
+    assert!(matches!(
+        dimacs::parse("p cnf 1 1\n1"),
+        Err(Error::Parse { line: 2, .. })
+    ));
+    assert!(matches!(
+        dimacs::parse("p cnf -1 1\n"),
+        Err(Error::Parse { line: 1, .. })
+    ));
+    // the header is only a hint
+    let instance = dimacs::parse("p cnf 99999999999 1\n2 0\n").unwrap();
+    assert_eq!(2, instance.vars.len());
+    let instance = dimacs::parse("p cnf 20 1\n1 0\n").unwrap();
+    assert_eq!(1, instance.vars.len());
+    let instance = dimacs::parse("p cnf 3 1\n1 0\n").unwrap();
+    assert_eq!(3, instance.vars.len());
+    assert!(matches!(
+        dimacs::parse("h 2000000000 0\n"),
+        Err(Error::Parse { line: 1, .. })
+    ));
+}
+
+#[test]
+fn opb_roundtrip() {
+    let text = "\
+* #variable= 2 #constraint= 2
+min: -1 x1 x2 +1 x2 ;
++1 x1 +1 ~x2 >= 1 ;
+-3 x1 x2 +2 x1 <= 1 ;
+";
+    let instance = opb::parse(text).unwrap();
+    assert_eq!(1, instance.num_products);
+    // The auxiliary variable of the product x1 x2 is x3.
+    let mut written = Vec::new();
+    opb::write(&instance.model, &mut written).unwrap();
+    let written = String::from_utf8(written).unwrap();
+    assert_eq!(
+        "\
+* #variable= 3 #constraint= 5
+min: -1 x3 +1 x2 ;
++1 ~x3 +1 x1 >= 1 ;
++1 ~x3 +1 x2 >= 1 ;
++1 ~x1 +1 ~x2 +1 x3 >= 1 ;
++1 x1 -1 x2 >= 0 ;
++3 x3 -2 x1 >= -1 ;
+",
+        written
+    );
+    assert_eq!(3, opb::parse(&written).unwrap().vars.len());
+}
+
+#[test]
+fn opb_enforcement() {
+    let mut proto = CpModelBuilder::default().proto().clone();
+    for _ in 0..3 {
+        proto.variables.push(IntegerVariableProto {
+            domain: vec![0, 1],
+            ..Default::default()
+        });
+    }
+    proto.constraints.push(ConstraintProto {
+        enforcement_literal: vec![-3],
+        constraint: Some(Constraint::Linear(LinearConstraintProto {
+            vars: vec![0, 1],
+            coeffs: vec![1, 1],
+            domain: vec![1, 1],
+        })),
+        ..Default::default()
+    });
+    let mut written = Vec::new();
+    opb::write(&CpModelBuilder::from(proto), &mut written).unwrap();
+    // Enforced by ~x3: x1 + x2 >= 1 and -x1 - x2 >= -1 hold if x3 is
+    // false.
+    assert_eq!(
+        "* #variable= 3 #constraint= 2\n+1 x1 +1 x2 +1 x3 >= 1 ;\n-1 x1 -1 x2 +1 x3 >= -1 ;\n",
+        String::from_utf8(written).unwrap()
+    );
+}
+
+#[test]
+fn opb_untrusted_sizes() {
+    // the header is only a hint
+    let instance = opb::parse("* #variable= 99999999999\n+1 x2 >= 1 ;\n").unwrap();
+    assert_eq!(2, instance.vars.len());
+    let instance = opb::parse("* #variable= 3\n+1 x2 >= 1 ;\n").unwrap();
+    assert_eq!(3, instance.vars.len());
+    for text in ["+1 x2000000000 >= 1 ;", "+1 y1 >= 1 ;", "+1 é1 >= 1 ;"] {
+        assert!(matches!(
+            opb::parse(text),
+            Err(Error::Parse { line: 1, .. })
+        ));
+    }
+}
+
+#[test]
+fn fzn_constraints_and_output() {
+    let text = "
+% A small scheduling model.
+predicate fzn_cumulative(array [int] of var int: s, array [int] of var int: d,
+                         array [int] of var int: r, var int: b);
+int: capacity = 2;
+array [1..2] of int: durations = [3, 2];
+var 0..10: s1 :: output_var;
+var 0..10: s2 :: output_var;
+var bool: b;
+var 0..20: makespan :: output_var;
+array [1..2] of var int: starts :: output_array([1..2]) = [s1, s2];
+constraint fzn_cumulative(starts, durations, [1, 2], capacity);
+constraint int_lin_le_reif([1, -1], [s1, s2], -3, b);
+constraint array_bool_or([b], true);
+constraint int_lin_le([1, -1], [s1, makespan], -3) :: domain;
+constraint fzn_table_int([s1, s2], [0, 3, 3, 0]);
+solve :: seq_search([int_search(starts, first_fail, indomain_min, complete),
+                     bool_search([b], input_order, indomain_max, complete)])
+      minimize makespan;
+";
+    let model = fzn::parse(text).unwrap();
+    let proto = model.model.proto();
+    let kinds: Vec<&str> = proto
+        .constraints
+        .iter()
+        .map(|c| match c.constraint.as_ref().unwrap() {
+            Constraint::Interval(_) => "interval",
+            Constraint::Cumulative(_) => "cumulative",
+            Constraint::Linear(_) => "linear",
+            Constraint::BoolOr(_) => "bool_or",
+            Constraint::Table(_) => "table",
+            _ => "other",
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            "interval",
+            "interval",
+            "cumulative",
+            "linear",
+            "linear",
+            "bool_or",
+            "bool_or",
+            "linear",
+            "table"
+        ],
+        kinds
+    );
+    assert_eq!(2, proto.search_strategy.len());
+
+    let solution = proto
+        .variables
+        .iter()
+        .map(|v| v.domain[0])
+        .collect::<Vec<_>>();
+    let response = CpSolverResponse {
+        status: CpSolverStatus::Optimal as i32,
+        solution,
+        ..Default::default()
+    };
+    assert_eq!(
+        "s1 = 0;\ns2 = 0;\nmakespan = 0;\nstarts = array1d(1..2, [0, 0]);\n----------\n==========\n",
+        model.output(&response)
+    );
+    let response = CpSolverResponse {
+        status: CpSolverStatus::Infeasible as i32,
+        ..Default::default()
+    };
+    assert_eq!("=====UNSATISFIABLE=====\n", model.output(&response));
+}
+
+#[test]
+fn fzn_errors() {
+    assert!(matches!(
+        fzn::parse("var float: x;"),
+        Err(Error::Unsupported(_))
+    ));
+    assert!(matches!(
+        fzn::parse("var 1..3: x;\nconstraint my_constraint(x);"),
+        Err(Error::Unsupported(_))
+    ));
+    assert!(matches!(
+        fzn::parse("var 1..3: x;\nconstraint int_le(x, y);"),
+        Err(Error::Parse { line: 2, .. })
+    ));
+    assert!(matches!(
+        fzn::parse("var 5..1: s;\nconstraint cumulative([s], [2], [1], 1);"),
+        Err(Error::Parse { line: 2, .. })
+    ));
+    assert!(matches!(
+        fzn::parse("array [1..99999999999] of var bool: b;"),
+        Err(Error::Parse { line: 1, .. })
+    ));
+    let max = i64::MAX - 1;
+    assert!(matches!(
+        fzn::parse(&format!(
+            "var 0..{}: s;\nconstraint cumulative([s], [{}], [1], 1);",
+            max, max
+        )),
+        Err(Error::Parse { line: 2, .. })
+    ));
+}
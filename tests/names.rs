@@ -0,0 +1,99 @@
+use cp_sat::builder::CpModelBuilder;
+use cp_sat::formats::{lp, mip::MipOptions};
+
+#[test]
+fn index_follows_renames() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var_with_name([(0, 10)], "x");
+    let y = model.new_bool_var_with_name("y");
+    let c = model.add_or([y]);
+    assert_eq!(model.var_by_name("x"), Some(x));
+    assert_eq!(model.var_by_name(""), None);
+
+    model.set_var_name(x, "y");
+    assert_eq!(model.var_by_name("x"), None);
+    assert_eq!(model.var_by_name("y"), Some(x));
+    model.set_var_name(x, "z");
+    assert_eq!(model.bool_var_by_name("y"), Some(y));
+
+    model.set_constraint_name(c, "or");
+    assert_eq!(model.constraint_by_name("or"), Some(c));
+    model.remove_constraint(c);
+    assert_eq!(model.constraint_by_name("or"), None);
+
+    let mut other = model.clone();
+    other.set_var_name(y, "w");
+    assert_eq!(model.var_by_name("w"), None);
+    assert_eq!(other.var_by_name("w"), Some(y.into()));
+}
+
+#[test]
+fn index_of_loaded_models() {
+    let text = "maximize\n obj: x + 2 y\nsubject to\n c1: x + y <= 4\nbounds\n 0 <= x <= 4\n 0 <= y <= 4\ngeneral\n x y\nend\n";
+    let lp = lp::parse(text, &MipOptions::default()).unwrap();
+    assert_eq!(lp.model.var_by_name("y"), Some(lp.columns[1].var));
+    assert!(lp.model.constraint_by_name("c1").is_some());
+}
+
+#[test]
+fn strict_names() {
+    let mut model = CpModelBuilder::default();
+    model.new_bool_var_with_name("a");
+    let b = model.new_bool_var_with_name("b");
+    let c = model.add_or([b]);
+    model.set_constraint_name(c, "c");
+    model.set_strict_names(true).unwrap();
+    // renaming to the same name, and unnamed variables, are allowed
+    model.set_var_name(b, "b");
+    model.new_bool_var();
+    model.new_bool_var();
+
+    let mut component = CpModelBuilder::default();
+    component.new_bool_var_with_name("z");
+    component.new_bool_var_with_name("a");
+    let error = model.try_merge(&component).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "variable #5 (a): name already used by variable #0"
+    );
+    // nothing is merged when a name is rejected
+    assert_eq!(model.proto().variables.len(), 4);
+    assert_eq!(model.var_by_name("z"), None);
+
+    // the names of the merged model must be unique too
+    let mut component = CpModelBuilder::default();
+    let x = component.new_bool_var();
+    let c1 = component.add_or([x]);
+    let c2 = component.add_or([x]);
+    component.set_constraint_name(c1, "d");
+    component.set_constraint_name(c2, "d");
+    let error = model.try_merge(&component).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "constraint #2 (d): name already used by constraint #1"
+    );
+    assert_eq!(model.proto().constraints.len(), 1);
+
+    let d = model.add_or([b]);
+    assert!(model.try_set_constraint_name(d, "c").is_err());
+    assert_eq!(model.constraint_name(d), "");
+    assert!(model.try_set_var_name(b, "a").is_err());
+    assert_eq!(model.var_name(b), "b");
+}
+
+#[test]
+fn strict_names_rejects_existing_duplicates() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_bool_var();
+    let c1 = model.add_or([x]);
+    let c2 = model.add_or([x]);
+    model.set_constraint_name(c1, "c");
+    model.set_constraint_name(c2, "c");
+    let error = model.set_strict_names(true).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "constraint #1 (c): name already used by constraint #0"
+    );
+    model.remove_constraint(c1);
+    assert!(model.set_strict_names(true).is_ok());
+}
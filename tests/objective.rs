@@ -0,0 +1,80 @@
+use cp_sat::builder::{CpModelBuilder, FloatLinearExpr, LinearExpr};
+
+#[test]
+fn objective_domain_of_maximization() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 100)]);
+    model.maximize([(2, x)]);
+    model.set_objective_domain([(10, 20), (50, i64::MAX)]);
+    // internally, -2x is minimized
+    let objective = model.proto().objective.as_ref().unwrap();
+    assert_eq!(objective.domain, [i64::MIN, -50, -20, -10]);
+}
+
+#[test]
+fn objective_domain_is_rounded_inwards() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 100)]);
+    model.minimize(x);
+    model.set_objective_scaling(2., 3.);
+    // the objective value is 2 * (x + 3)
+    model.set_objective_domain([(3, 8), (9, 9)]);
+    let objective = model.proto().objective.as_ref().unwrap();
+    assert_eq!(objective.domain, [-1, 1]);
+}
+
+#[test]
+fn objective_domain_is_merged() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 100)]);
+    model.minimize(x);
+    model.set_objective_domain([(20, 30), (0, 5), (25, 40), (6, 10), (42, 50)]);
+    let objective = model.proto().objective.as_ref().unwrap();
+    assert_eq!(objective.domain, [0, 10, 20, 40, 42, 50]);
+}
+
+#[test]
+fn objective_domain_with_zero_scaling_factor() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 100)]);
+    model.minimize(x);
+    // a scaling factor of 0 is read as 1 by the solver
+    model.set_objective_scaling(0., 3.);
+    model.set_objective_domain([(5, 10)]);
+    let objective = model.proto().objective.as_ref().unwrap();
+    assert_eq!(objective.domain, [2, 7]);
+}
+
+#[test]
+fn objective_read_back() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 100)]);
+    assert!(model.objective().is_none());
+    model.maximize(LinearExpr::from([(2, x)]) + 7);
+    let expr = model.objective().unwrap();
+    model.minimize(expr);
+    let objective = model.proto().objective.as_ref().unwrap();
+    assert_eq!(objective.coeffs, [2]);
+    assert_eq!(objective.offset, 7.);
+    assert_eq!(objective.scaling_factor, 1.);
+}
+
+#[test]
+fn float_objective_scaling() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 100)]);
+    let y = model.new_bool_var();
+    let expr = FloatLinearExpr::from([(0.25, x), (1.5, (!y).into())]) + 2.;
+    assert_eq!(model.maximize_float(expr, 3), 0.);
+    let objective = model.proto().objective.as_ref().unwrap();
+    // 0.25x + 1.5(1 - y) + 2 = 0.25x - 1.5y + 3.5, times 100
+    assert_eq!(objective.coeffs, [-25, 150]);
+    assert_eq!(objective.offset, -350.);
+    assert_eq!(objective.scaling_factor, -0.01);
+
+    let error = model.minimize_float([(2. / 3., x)], 1);
+    assert!((error - 1. / 30.).abs() < 1e-12);
+    let objective = model.proto().objective.as_ref().unwrap();
+    assert_eq!(objective.coeffs, [7]);
+    assert_eq!(objective.scaling_factor, 0.1);
+}
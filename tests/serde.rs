@@ -0,0 +1,35 @@
+#![cfg(feature = "serde")]
+
+use cp_sat::builder::{BoolVar, CpModelBuilder, IntVar, LinearExpr};
+use cp_sat::proto::CpModelProto;
+
+#[test]
+fn model_proto_roundtrip() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var_with_name([(0, 10)], "x");
+    let y = model.new_bool_var_with_name("y");
+    model.add_or([y, !y]);
+    model.add_le([(2, x), (3, y.into())], 12);
+    model.maximize(x);
+
+    let json = serde_json::to_string(model.proto()).unwrap();
+    let proto: CpModelProto = serde_json::from_str(&json).unwrap();
+    assert_eq!(&proto, model.proto());
+}
+
+#[test]
+fn handles_roundtrip() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 10)]);
+    let y = model.new_bool_var();
+
+    let json = serde_json::to_string(&(x, !y)).unwrap();
+    let (x2, y2): (IntVar, BoolVar) = serde_json::from_str(&json).unwrap();
+    assert_eq!(x, x2);
+    assert_eq!(!y, y2);
+
+    let expr = LinearExpr::from([(2, x), (3, y.into())]) + 5;
+    let json = serde_json::to_string(&expr).unwrap();
+    let expr2: LinearExpr = serde_json::from_str(&json).unwrap();
+    assert_eq!(format!("{:?}", expr), format!("{:?}", expr2));
+}
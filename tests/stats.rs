@@ -0,0 +1,38 @@
+use cp_sat::builder::CpModelBuilder;
+use cp_sat::proto::CpSolverStatus;
+use cp_sat::stats::{SolveStats, SolveStatsAggregate};
+
+#[test]
+fn solve_stats_from_response() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 10)]);
+    let y = model.new_int_var([(0, 10)]);
+    model.add_le([(2, x), (3, y)], 12);
+    model.maximize([(1, x), (1, y)]);
+
+    let response = model.solve();
+    let stats = SolveStats::from_response(&response);
+    println!("{}", stats);
+
+    assert_eq!(stats.status(), CpSolverStatus::Optimal);
+    assert_eq!(stats.objective_value, 6.);
+    assert_eq!(stats.relative_gap(), 0.);
+
+    let aggregate: SolveStatsAggregate = [&stats, &stats].iter().copied().collect();
+    assert_eq!(aggregate.num_runs, 2);
+    assert_eq!(aggregate.num_optimal, 2);
+    assert_eq!(aggregate.total_conflicts, 2 * stats.num_conflicts);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn solve_stats_serde() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_bool_var();
+    model.add_or([x]);
+
+    let stats = SolveStats::from_response(&model.solve());
+    let json = serde_json::to_string(&stats).unwrap();
+    let back: SolveStats = serde_json::from_str(&json).unwrap();
+    assert_eq!(stats, back);
+}
@@ -0,0 +1,168 @@
+use cp_sat::builder::CpModelBuilder;
+use cp_sat::dot::{to_dot, DotOptions};
+use cp_sat::lint::{lint, LintOptions};
+use cp_sat::proto::{self, constraint_proto::Constraint};
+use cp_sat::validation::{validate, validate_hints, Location, ValidationErrorKind};
+
+#[test]
+fn valid_model() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 10)]);
+    let y = model.new_bool_var();
+    model.add_le([(2, x)], 5);
+    model.add_or([y, !y]);
+    model.maximize(x);
+    assert!(model.validate().is_empty());
+}
+
+#[test]
+fn reports_all_errors() {
+    let model = proto::CpModelProto {
+        variables: vec![
+            proto::IntegerVariableProto {
+                name: "x".into(),
+                domain: vec![0, i64::MAX],
+            },
+            proto::IntegerVariableProto {
+                name: "y".into(),
+                domain: vec![0, 1],
+            },
+        ],
+        constraints: vec![
+            proto::ConstraintProto {
+                name: "all_diff".into(),
+                enforcement_literal: vec![1],
+                constraint: Some(Constraint::AllDiff(proto::AllDifferentConstraintProto {
+                    vars: vec![0, 2],
+                })),
+            },
+            proto::ConstraintProto {
+                constraint: Some(Constraint::Linear(proto::LinearConstraintProto {
+                    vars: vec![0, 1],
+                    coeffs: vec![4],
+                    domain: vec![0, 10],
+                })),
+                ..Default::default()
+            },
+            proto::ConstraintProto {
+                constraint: Some(Constraint::NoOverlap(proto::NoOverlapConstraintProto {
+                    intervals: vec![0],
+                })),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let errors = validate(&model);
+    let kinds: Vec<_> = errors.iter().map(|e| e.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            ValidationErrorKind::DomainTooLarge,
+            ValidationErrorKind::UnsupportedEnforcement,
+            ValidationErrorKind::InvalidVariableIndex,
+            ValidationErrorKind::SizeMismatch,
+            ValidationErrorKind::InvalidIntervalIndex,
+        ]
+    );
+    assert_eq!(
+        errors[1].location,
+        Location::Constraint {
+            index: 0,
+            name: "all_diff".into()
+        }
+    );
+}
+
+#[test]
+fn reports_hint_errors() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 2), (5, 10)]);
+    let y = model.new_bool_var();
+    model.add_hint(x, 3);
+    model.add_hint(!y, 1);
+    model.add_hint(y, 1);
+    let mut model = model.proto().clone();
+    let hint = model.solution_hint.as_mut().unwrap();
+    // negated literal hinted false, i.e. y = 1
+    hint.vars.push(-2);
+    hint.values.push(0);
+    // ignored invalid reference
+    hint.vars.push(7);
+    hint.values.push(0);
+    let errors = validate_hints(&model);
+    let kinds: Vec<_> = errors.iter().map(|e| e.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            ValidationErrorKind::HintOutOfDomain,
+            ValidationErrorKind::DuplicateHint,
+            ValidationErrorKind::DuplicateHint,
+        ]
+    );
+    assert!(errors.iter().all(|e| e.location == Location::SolutionHint));
+    assert_eq!(
+        errors[1].to_string(),
+        "solution hint: variable #1 is hinted several times"
+    );
+}
+
+#[test]
+fn reports_non_boolean_literals() {
+    let mut model = CpModelBuilder::default();
+    let x = model.new_int_var([(0, 10)]);
+    model.new_bool_var();
+    model.new_int_var([(-1, 0)]);
+    model.add_le(x, 5);
+    let mut model = model.proto().clone();
+    // enforced by x, in [0, 10]
+    model.constraints[0].enforcement_literal.push(0);
+    model.constraints.push(proto::ConstraintProto {
+        constraint: Some(Constraint::BoolOr(proto::BoolArgumentProto {
+            literals: vec![1, -2, -3],
+        })),
+        ..Default::default()
+    });
+    let errors = validate(&model);
+    let kinds: Vec<_> = errors.iter().map(|e| e.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            ValidationErrorKind::NonBooleanLiteral,
+            ValidationErrorKind::NonBooleanLiteral,
+        ]
+    );
+    assert_eq!(
+        errors[1].to_string(),
+        "constraint #1: literal -3 refers to variable #2 with domain [-1, 0], which is not included in [0, 1]"
+    );
+}
+
+#[test]
+fn invalid_references_do_not_panic() {
+    let model = proto::CpModelProto {
+        variables: vec![proto::IntegerVariableProto {
+            name: "x".into(),
+            domain: vec![0, 1],
+        }],
+        constraints: vec![
+            proto::ConstraintProto {
+                enforcement_literal: vec![i32::MIN],
+                constraint: Some(Constraint::BoolOr(proto::BoolArgumentProto {
+                    literals: vec![0, i32::MIN],
+                })),
+                ..Default::default()
+            },
+            proto::ConstraintProto {
+                constraint: Some(Constraint::NoOverlap(proto::NoOverlapConstraintProto {
+                    intervals: vec![-1, i32::MIN],
+                })),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    assert!(!validate(&model).is_empty());
+    lint(&model, &LintOptions::default());
+    to_dot(&model, &DotOptions::default());
+}